@@ -0,0 +1,158 @@
+//! An `Aggregator` collects partial counts from worker threads over a channel. Workers
+//! send whole `CountingRatio`s or batches of raw observations through an
+//! `AggregatorSender`, and a collector thread adds them into a running total, optionally
+//! passing a snapshot of the total to a callback at regular intervals.
+//!
+//! ```
+//! use counting_ratio::{Aggregator, CountingRatio};
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! let aggregator = Aggregator::spawn();
+//! let workers: Vec<_> = (0..4)
+//!     .map(|worker| {
+//!         let sender = aggregator.sender();
+//!         thread::spawn(move || {
+//!             sender.send(CountingRatio::ratio(worker, 10));
+//!             sender.send_batch(vec![true, false]);
+//!         })
+//!     })
+//!     .collect();
+//! for worker in workers {
+//!     worker.join().unwrap();
+//! }
+//!
+//! assert_eq!(aggregator.finish(), CountingRatio::ratio(10, 48));
+//!
+//! let (reports, received) = std::sync::mpsc::channel();
+//! let aggregator = Aggregator::spawn_with_snapshots(Duration::from_millis(5), move |total| {
+//!     let _ = reports.send(total);
+//! });
+//! aggregator.sender().send(CountingRatio::ratio(1, 2));
+//! assert!(received.iter().any(|total| total == CountingRatio::ratio(1, 2)));
+//! assert_eq!(aggregator.snapshot(), CountingRatio::ratio(1, 2));
+//! ```
+
+use crate::CountingRatio;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+enum Update {
+    Partial(CountingRatio),
+    Batch(Vec<bool>),
+}
+
+/// Sends counts to an `Aggregator`. Cloning it gives another worker its own sender.
+#[derive(Clone, Debug)]
+pub struct AggregatorSender {
+    sender: Sender<Update>,
+}
+
+impl AggregatorSender {
+    /// Sends counts made by the worker. Counts sent after the aggregator finishes are
+    /// discarded.
+    pub fn send(&self, partial: CountingRatio) {
+        let _ = self.sender.send(Update::Partial(partial));
+    }
+
+    /// Sends raw observations, which the collector thread counts.
+    pub fn send_batch(&self, observations: Vec<bool>) {
+        let _ = self.sender.send(Update::Batch(observations));
+    }
+}
+
+#[derive(Debug)]
+pub struct Aggregator {
+    sender: AggregatorSender,
+    total: Arc<Mutex<CountingRatio>>,
+    collector: JoinHandle<CountingRatio>,
+}
+
+impl Aggregator {
+    /// Starts a collector thread.
+    pub fn spawn() -> Self {
+        Self::start(None::<(Duration, fn(CountingRatio))>)
+    }
+
+    /// Starts a collector thread that passes a snapshot of the total to `callback` once
+    /// every `interval`, beginning one `interval` after spawning.
+    pub fn spawn_with_snapshots<F: FnMut(CountingRatio) + Send + 'static>(
+        interval: Duration,
+        callback: F,
+    ) -> Self {
+        Self::start(Some((interval, callback)))
+    }
+
+    fn start<F: FnMut(CountingRatio) + Send + 'static>(reporting: Option<(Duration, F)>) -> Self {
+        let (sender, receiver) = channel();
+        let total = Arc::new(Mutex::new(CountingRatio::new()));
+        let shared = total.clone();
+        let collector = thread::spawn(move || collect(receiver, &shared, reporting));
+        Self {
+            sender: AggregatorSender { sender },
+            total,
+            collector,
+        }
+    }
+
+    pub fn sender(&self) -> AggregatorSender {
+        self.sender.clone()
+    }
+
+    /// The total of the counts collected so far. Counts still in the channel are not
+    /// included.
+    pub fn snapshot(&self) -> CountingRatio {
+        *self
+            .total
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Waits until every sender has been dropped and all of their counts collected, then
+    /// returns the total.
+    pub fn finish(self) -> CountingRatio {
+        drop(self.sender);
+        match self.collector.join() {
+            Ok(total) => total,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+fn collect<F: FnMut(CountingRatio)>(
+    receiver: Receiver<Update>,
+    shared: &Mutex<CountingRatio>,
+    mut reporting: Option<(Duration, F)>,
+) -> CountingRatio {
+    let mut total = CountingRatio::new();
+    let mut next_report = reporting
+        .as_ref()
+        .map(|(interval, _)| Instant::now() + *interval);
+    loop {
+        let update = match next_report {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match update {
+            Ok(Update::Partial(partial)) => total += partial,
+            Ok(Update::Batch(observations)) => total.observe_slice(&observations),
+            Err(RecvTimeoutError::Timeout) => {
+                if let (Some((interval, callback)), Some(deadline)) =
+                    (reporting.as_mut(), next_report.as_mut())
+                {
+                    callback(total);
+                    *deadline += *interval;
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => return total,
+        }
+        *shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = total;
+    }
+}
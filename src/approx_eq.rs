@@ -0,0 +1,95 @@
+//! Tolerant comparisons of counting ratios through the `approx` crate, which compare the
+//! proportions the ratios imply rather than their counts. Two undefined ratios are
+//! approximately equal, while an undefined ratio is never approximately equal to a defined
+//! one. Requires the `approx` feature.
+//!
+//! ```
+//! use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne};
+//! use counting_ratio::{CountingRatio, Probability};
+//!
+//! let observed = CountingRatio::ratio(333, 1000);
+//! assert_relative_eq!(observed, CountingRatio::ratio(1, 3), epsilon = 0.001);
+//! assert_relative_ne!(observed, CountingRatio::ratio(1, 3));
+//! assert_abs_diff_eq!(CountingRatio::ratio(3, 10), CountingRatio::ratio(6, 20));
+//! assert_relative_ne!(CountingRatio::new(), CountingRatio::ratio(0, 1));
+//!
+//! let both = Probability::new(1, 2).unwrap() * Probability::new(1, 3).unwrap();
+//! assert_relative_eq!(both, Probability::new(167, 1000).unwrap(), max_relative = 0.01);
+//! ```
+
+use crate::{Count, GenericCountingRatio, Probability};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+impl<T: Count> AbsDiffEq for GenericCountingRatio<T> {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => a.abs_diff_eq(&b, epsilon),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl<T: Count> RelativeEq for GenericCountingRatio<T> {
+    fn default_max_relative() -> f64 {
+        f64::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => a.relative_eq(&b, epsilon, max_relative),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl<T: Count> UlpsEq for GenericCountingRatio<T> {
+    fn default_max_ulps() -> u32 {
+        f64::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: f64, max_ulps: u32) -> bool {
+        match (self.value(), other.value()) {
+            (Some(a), Some(b)) => a.ulps_eq(&b, epsilon, max_ulps),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl AbsDiffEq for Probability {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> f64 {
+        f64::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.value().abs_diff_eq(&other.value(), epsilon)
+    }
+}
+
+impl RelativeEq for Probability {
+    fn default_max_relative() -> f64 {
+        f64::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: f64, max_relative: f64) -> bool {
+        self.value()
+            .relative_eq(&other.value(), epsilon, max_relative)
+    }
+}
+
+impl UlpsEq for Probability {
+    fn default_max_ulps() -> u32 {
+        f64::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: f64, max_ulps: u32) -> bool {
+        self.value().ulps_eq(&other.value(), epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,91 @@
+//! A tokio task that periodically snapshots counters shared with the rest of an async
+//! service and hands the snapshots to a callback, which might log them or push them to a
+//! metrics gateway. Requires the `tokio` feature.
+//!
+//! ```
+//! use counting_ratio::{spawn_reporter, AtomicCountingRatio, CountingRatio};
+//! use std::sync::{Arc, Mutex};
+//! use std::time::Duration;
+//!
+//! let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+//! runtime.block_on(async {
+//!     let requests = Arc::new(AtomicCountingRatio::new());
+//!     let reports = Arc::new(Mutex::new(Vec::new()));
+//!     let received = reports.clone();
+//!     let task = spawn_reporter(
+//!         Duration::from_millis(5),
+//!         [("requests", requests.clone())],
+//!         move |snapshots| received.lock().unwrap().push(snapshots.to_vec()),
+//!     );
+//!
+//!     requests.observe(true);
+//!     while reports.lock().unwrap().is_empty() {
+//!         tokio::time::sleep(Duration::from_millis(1)).await;
+//!     }
+//!     task.abort();
+//!     let first = reports.lock().unwrap()[0].clone();
+//!     assert_eq!(first, vec![("requests".to_string(), CountingRatio::ratio(1, 1))]);
+//! });
+//! ```
+
+use crate::{AtomicCountingRatio, CountingRatio};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant};
+
+/// A counter that can be read while other tasks update it.
+pub trait SharedRatio: Send + Sync {
+    fn snapshot(&self) -> CountingRatio;
+}
+
+impl SharedRatio for AtomicCountingRatio {
+    fn snapshot(&self) -> CountingRatio {
+        AtomicCountingRatio::snapshot(self)
+    }
+}
+
+impl SharedRatio for Mutex<CountingRatio> {
+    /// Reads the ratio even if a thread panicked while holding the lock.
+    fn snapshot(&self) -> CountingRatio {
+        *self.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SharedRatio for RwLock<CountingRatio> {
+    fn snapshot(&self) -> CountingRatio {
+        *self.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Spawns a task on the current tokio runtime that, once every `interval`, passes a
+/// snapshot of every named counter to `callback`. The first report comes one `interval`
+/// after spawning. The task runs until it is aborted. Panics if `interval` is zero.
+pub fn spawn_reporter<N, C, I, F>(
+    interval: Duration,
+    counters: I,
+    mut callback: F,
+) -> JoinHandle<()>
+where
+    N: Into<String>,
+    C: SharedRatio + ?Sized + 'static,
+    I: IntoIterator<Item = (N, Arc<C>)>,
+    F: FnMut(&[(String, CountingRatio)]) + Send + 'static,
+{
+    assert!(!interval.is_zero(), "report interval must be positive");
+    let counters: Vec<(String, Arc<C>)> = counters
+        .into_iter()
+        .map(|(name, counter)| (name.into(), counter))
+        .collect();
+    tokio::spawn(async move {
+        let mut ticks = interval_at(Instant::now() + interval, interval);
+        loop {
+            ticks.tick().await;
+            let snapshots: Vec<(String, CountingRatio)> = counters
+                .iter()
+                .map(|(name, counter)| (name.clone(), counter.snapshot()))
+                .collect();
+            callback(&snapshots);
+        }
+    })
+}
@@ -0,0 +1,76 @@
+//! An `AtomicCountingRatio` can record observations from many threads at once without
+//! a surrounding lock. A `snapshot()` yields an ordinary `CountingRatio`.
+//!
+//! ```
+//! use counting_ratio::{AtomicCountingRatio, CountingRatio};
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let shared = Arc::new(AtomicCountingRatio::new());
+//! let workers: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let shared = shared.clone();
+//!         thread::spawn(move || {
+//!             for i in 0..100 {
+//!                 shared.observe(i % 4 == 0);
+//!             }
+//!         })
+//!     })
+//!     .collect();
+//! for worker in workers {
+//!     worker.join().unwrap();
+//! }
+//!
+//! assert_eq!(shared.snapshot(), CountingRatio::ratio(100, 400));
+//! ```
+
+use crate::CountingRatio;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+pub struct AtomicCountingRatio {
+    matches: AtomicU64,
+    observations: AtomicU64,
+}
+
+impl AtomicCountingRatio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Observations are always incremented before matches, and snapshots read matches
+    // before observations, so a snapshot never shows more matches than observations.
+    pub fn observe(&self, condition_met: bool) {
+        self.observations.fetch_add(1, Ordering::Relaxed);
+        if condition_met {
+            self.matches.fetch_add(1, Ordering::Release);
+        }
+    }
+
+    pub fn observe_with_prior(&self, prior_condition_met: bool, posterior_condition_met: bool) {
+        if prior_condition_met {
+            self.observe(posterior_condition_met);
+        }
+    }
+
+    pub fn add(&self, counts: CountingRatio) {
+        self.observations
+            .fetch_add(counts.observations, Ordering::Relaxed);
+        self.matches.fetch_add(counts.matches, Ordering::Release);
+    }
+
+    pub fn snapshot(&self) -> CountingRatio {
+        let matches = self.matches.load(Ordering::Acquire);
+        let observations = self.observations.load(Ordering::Relaxed);
+        CountingRatio::ratio(matches, observations)
+    }
+}
+
+impl From<CountingRatio> for AtomicCountingRatio {
+    fn from(counts: CountingRatio) -> Self {
+        Self {
+            matches: AtomicU64::new(counts.matches),
+            observations: AtomicU64::new(counts.observations),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Multi-armed bandit strategies that choose which arm to play next based on the
+//! `CountingRatio` of successes observed for each arm.
+//!
+//! The UCB1 strategy deterministically plays the arm that maximizes its observed success
+//! rate plus an exploration bonus, which shrinks as the arm is played more often. Arms
+//! that have never been played are chosen first.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//! use counting_ratio::bandit::{ucb1_select, Ucb1};
+//!
+//! let arms = [CountingRatio::ratio(6, 10), CountingRatio::ratio(3, 4), CountingRatio::ratio(40, 100)];
+//! assert_eq!(ucb1_select(&arms), Some(1));
+//! assert_eq!(ucb1_select(&[CountingRatio::ratio(6, 10), CountingRatio::new()]), Some(1));
+//!
+//! let mut ucb = Ucb1::new(["red", "blue"]);
+//! ucb.record("red", true);
+//! assert_eq!(ucb.choose(), Some("blue"));
+//! ```
+
+use crate::{CountingRatio, GroupedRatio};
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand_distr::{Beta, Distribution};
+
+/// Samples each arm's Beta posterior and plays the arm with the highest sample. Requires
+/// the `rand` feature.
+///
+/// ```
+/// use counting_ratio::bandit::ThompsonSampler;
+///
+/// let mut sampler = ThompsonSampler::new(["red", "blue"]);
+/// for _ in 0..50 {
+///     sampler.record("red", false);
+///     sampler.record("blue", true);
+/// }
+///
+/// let mut rng = rand::rng();
+/// assert_eq!(sampler.choose(&mut rng), Some("blue"));
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct ThompsonSampler<A: Ord + Clone> {
+    arms: GroupedRatio<A>,
+    alpha: f64,
+    beta: f64,
+}
+
+#[cfg(feature = "rand")]
+impl<A: Ord + Clone> ThompsonSampler<A> {
+    /// Uses a uniform Beta(1, 1) prior for every arm.
+    pub fn new<I: IntoIterator<Item = A>>(arms: I) -> Self {
+        Self::with_prior(arms, 1.0, 1.0)
+    }
+
+    /// Uses a Beta(`alpha`, `beta`) prior for every arm. Both parameters must be positive.
+    pub fn with_prior<I: IntoIterator<Item = A>>(arms: I, alpha: f64, beta: f64) -> Self {
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        let mut result = Self {
+            arms: GroupedRatio::new(),
+            alpha,
+            beta,
+        };
+        for arm in arms {
+            result.add_arm(arm);
+        }
+        result
+    }
+
+    pub fn add_arm(&mut self, arm: A) {
+        self.arms.add(arm, CountingRatio::new());
+    }
+
+    /// Records the outcome of playing `arm`, adding the arm if it is new.
+    pub fn record(&mut self, arm: A, success: bool) {
+        self.arms.observe(arm, success);
+    }
+
+    pub fn arms(&self) -> &GroupedRatio<A> {
+        &self.arms
+    }
+
+    /// Samples every arm's posterior and returns the arm with the highest sample, or
+    /// `None` if there are no arms.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<A> {
+        let mut best: Option<(&A, f64)> = None;
+        for (arm, ratio) in self.arms.iter() {
+            let posterior = ratio.posterior(self.alpha, self.beta);
+            let (alpha, beta) = posterior.posterior_parameters();
+            let sample = Beta::new(alpha, beta)
+                .expect("posterior parameters are positive")
+                .sample(rng);
+            if best.is_none_or(|(_, best_sample)| sample > best_sample) {
+                best = Some((arm, sample));
+            }
+        }
+        best.map(|(arm, _)| arm.clone())
+    }
+}
+
+/// Returns the index of the arm that UCB1 plays next, or `None` if `arms` is empty. Ties
+/// go to the earliest arm.
+pub fn ucb1_select(arms: &[CountingRatio]) -> Option<usize> {
+    let total_plays = arms.iter().map(|ratio| ratio.observations()).sum();
+    best_ucb1(arms.iter().copied().enumerate(), total_plays)
+}
+
+fn best_ucb1<A, I: Iterator<Item = (A, CountingRatio)>>(arms: I, total_plays: u64) -> Option<A> {
+    let total_plays = total_plays as f64;
+    let mut best: Option<(A, f64)> = None;
+    for (arm, ratio) in arms {
+        let score = ucb1_score(ratio, total_plays);
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_score)| score > *best_score)
+        {
+            best = Some((arm, score));
+        }
+    }
+    best.map(|(arm, _)| arm)
+}
+
+fn ucb1_score(ratio: CountingRatio, total_plays: f64) -> f64 {
+    match ratio.value() {
+        None => f64::INFINITY,
+        Some(mean) => {
+            let plays = ratio.observations() as f64;
+            mean + (2.0 * total_plays.ln() / plays).sqrt()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ucb1<A: Ord + Clone> {
+    arms: GroupedRatio<A>,
+}
+
+impl<A: Ord + Clone> Ucb1<A> {
+    pub fn new<I: IntoIterator<Item = A>>(arms: I) -> Self {
+        let mut result = Self {
+            arms: GroupedRatio::new(),
+        };
+        for arm in arms {
+            result.add_arm(arm);
+        }
+        result
+    }
+
+    pub fn add_arm(&mut self, arm: A) {
+        self.arms.add(arm, CountingRatio::new());
+    }
+
+    /// Records the outcome of playing `arm`, adding the arm if it is new.
+    pub fn record(&mut self, arm: A, success: bool) {
+        self.arms.observe(arm, success);
+    }
+
+    pub fn arms(&self) -> &GroupedRatio<A> {
+        &self.arms
+    }
+
+    /// Returns the arm to play next, or `None` if there are no arms. Ties go to the
+    /// smallest arm.
+    pub fn choose(&self) -> Option<A> {
+        best_ucb1(self.arms.iter(), self.arms.pooled().observations()).cloned()
+    }
+}
@@ -0,0 +1,53 @@
+//! A terminal-friendly rendering of a ratio as a bar of filled and empty blocks, followed by
+//! the percentage and the counts. The formatter's precision sets the decimal places of the
+//! percentage, which defaults to one.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let passed = CountingRatio::ratio(412, 775);
+//! assert_eq!(passed.bar(13).to_string(), "███████░░░░░░ 53.2% (412/775)");
+//! assert_eq!(format!("{:.2}", passed.bar(4)), "██░░ 53.16% (412/775)");
+//! assert_eq!(CountingRatio::new().bar(4).to_string(), "░░░░ NaN% (0/0)");
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+use core::fmt::{Display, Formatter, Write};
+
+/// A ratio displayed as a bar, as returned by `bar()`.
+#[derive(Copy, Clone, Debug)]
+pub struct RatioBar<T: Count = u64> {
+    ratio: GenericCountingRatio<T>,
+    width: usize,
+}
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// Displays the ratio as a bar `width` blocks wide, filled in proportion to the
+    /// ratio's value. An undefined ratio has an empty bar.
+    pub fn bar(&self, width: usize) -> RatioBar<T> {
+        RatioBar {
+            ratio: *self,
+            width,
+        }
+    }
+}
+
+impl<T: Count> Display for RatioBar<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let filled = match self.ratio.value() {
+            Some(value) => ((value * self.width as f64 + 0.5) as usize).min(self.width),
+            None => 0,
+        };
+        for block in 0..self.width {
+            f.write_char(if block < filled { '█' } else { '░' })?;
+        }
+        let precision = f.precision().unwrap_or(1);
+        write!(
+            f,
+            " {:#.precision$} ({}/{})",
+            self.ratio,
+            self.ratio.matches(),
+            self.ratio.observations()
+        )
+    }
+}
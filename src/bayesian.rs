@@ -0,0 +1,372 @@
+use crate::{compare_fractions, gcd, CountingRatio};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign};
+use histogram_macros::*;
+use trait_set::trait_set;
+
+trait_set! {
+    pub trait Countable = Clone + Ord + Debug;
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BayesianCounter<L: Countable, S: Countable> {
+    counts: BTreeMap<L, BTreeMap<S, u64>>,
+    total: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    smoothing: u64,
+}
+
+impl<L: Countable, S: Countable> BayesianCounter<L, S> {
+    pub fn new() -> Self {
+        Self::with_smoothing(0)
+    }
+
+    /// Adds `alpha` pseudo-observations of every known example to every label when
+    /// computing `p_example_given_label()`, so that unseen examples do not receive a
+    /// probability of zero. An `alpha` of 1 is Laplace smoothing.
+    pub fn with_smoothing(alpha: u64) -> Self {
+        Self {
+            counts: BTreeMap::new(),
+            total: 0,
+            smoothing: alpha,
+        }
+    }
+
+    pub fn smoothing(&self) -> u64 {
+        self.smoothing
+    }
+
+    pub fn observe(&mut self, example: S, label: L) {
+        match self.counts.get_mut(&label) {
+            Some(counter) => {
+                bump!(counter, example);
+            }
+            None => {
+                let mut counter = BTreeMap::new();
+                bump!(counter, example);
+                self.counts.insert(label, counter);
+            }
+        };
+        self.total += 1;
+    }
+
+    // Every nonzero count, ordered by label and then by example.
+    #[cfg(any(feature = "csv", feature = "arrow"))]
+    pub(crate) fn nonzero_counts(&self) -> impl Iterator<Item = (&L, &S, u64)> {
+        self.counts.iter().flat_map(|(label, counter)| {
+            counter
+                .iter()
+                .map(move |(example, count)| (label, example, *count))
+        })
+    }
+
+    // Records `count` observations of `example` with `label` at once.
+    #[cfg(feature = "csv")]
+    pub(crate) fn observe_count(&mut self, example: S, label: L, count: u64) {
+        if count > 0 {
+            *self
+                .counts
+                .entry(label)
+                .or_default()
+                .entry(example)
+                .or_default() += count;
+            self.total += count;
+        }
+    }
+
+    /// Multiplies every count by `factor`, which must lie within `[0.0, 1.0]`, rounding down.
+    /// Decaying periodically lets recent observations outweigh stale ones, so that the
+    /// counter adapts as the underlying distribution drifts.
+    ///
+    /// ```
+    /// use counting_ratio::{BayesianCounter, CountingRatio};
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for _ in 0..10 {
+    ///     bayesian.observe("old", true);
+    /// }
+    /// bayesian.decay(0.25);
+    /// for _ in 0..4 {
+    ///     bayesian.observe("new", true);
+    /// }
+    ///
+    /// assert_eq!(bayesian.count("old", true), 2);
+    /// assert_eq!(bayesian.p_example("new"), CountingRatio::ratio(4, 6));
+    /// ```
+    pub fn decay(&mut self, factor: f64) {
+        assert!(
+            (0.0..=1.0).contains(&factor),
+            "decay factor must lie within [0.0, 1.0]"
+        );
+        for counter in self.counts.values_mut() {
+            for count in counter.values_mut() {
+                *count = (*count as f64 * factor) as u64;
+            }
+            counter.retain(|_, count| *count > 0);
+        }
+        self.counts.retain(|_, counter| !counter.is_empty());
+        self.total = self.counts.values().flat_map(|t| t.values()).sum();
+    }
+
+    /// Adds every count of `other` to this counter, as when combining counters built on
+    /// separate shards of data.
+    pub fn merge(&mut self, other: &Self) {
+        for (label, counter) in other.counts.iter() {
+            let mine = self.counts.entry(label.clone()).or_default();
+            for (example, count) in counter.iter() {
+                *mine.entry(example.clone()).or_default() += *count;
+            }
+        }
+        self.total += other.total;
+    }
+
+    pub fn count(&self, example: S, label: L) -> u64 {
+        self.count_of(&example, &label)
+    }
+
+    pub fn label_count(&self, label: L) -> u64 {
+        self.label_count_of(&label)
+    }
+
+    pub fn example_count(&self, example: S) -> u64 {
+        self.example_count_of(&example)
+    }
+
+    // The by-reference counterparts of the counting methods avoid cloning keys that are
+    // not `Copy`.
+    pub(crate) fn count_of(&self, example: &S, label: &L) -> u64 {
+        self.counts
+            .get(label)
+            .map_or(0, |t| t.get(example).copied().unwrap_or(0))
+    }
+
+    pub(crate) fn label_count_of(&self, label: &L) -> u64 {
+        self.counts.get(label).map_or(0, |t| t.values().sum())
+    }
+
+    pub(crate) fn example_count_of(&self, example: &S) -> u64 {
+        self.counts
+            .values()
+            .map(|t| t.get(example).copied().unwrap_or(0))
+            .sum()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Every label observed so far, in ascending order.
+    pub fn labels(&self) -> impl Iterator<Item = L> + '_ {
+        self.counts.keys().cloned()
+    }
+
+    /// Every distinct example observed so far, under any label, in ascending order.
+    pub fn examples(&self) -> BTreeSet<S> {
+        self.counts
+            .values()
+            .flat_map(|t| t.keys().cloned())
+            .collect()
+    }
+
+    pub fn p_label(&self, label: L) -> CountingRatio {
+        CountingRatio::ratio(self.label_count_of(&label), self.total)
+    }
+
+    pub fn p_example(&self, example: S) -> CountingRatio {
+        CountingRatio::ratio(self.example_count_of(&example), self.total)
+    }
+
+    pub fn p_example_given_label(&self, example: S, label: L) -> CountingRatio {
+        self.p_example_given_label_of(&example, &label)
+    }
+
+    pub(crate) fn p_example_given_label_of(&self, example: &S, label: &L) -> CountingRatio {
+        CountingRatio::ratio(
+            self.count_of(example, label) + self.smoothing,
+            self.label_count_of(label) + self.smoothing * self.distinct_example_count(),
+        )
+    }
+
+    fn distinct_example_count(&self) -> u64 {
+        self.examples().len() as u64
+    }
+
+    pub fn p_label_given_example(&self, label: L, example: S) -> CountingRatio {
+        self.p_example_given_label_of(&example, &label) * self.p_label(label)
+            / self.p_example(example)
+    }
+
+    /// Every label, from most to least probable given `example`. Ties are broken in favor
+    /// of the smallest label, so the first label is the one `classify()` returns.
+    ///
+    /// ```
+    /// use counting_ratio::{BayesianCounter, CountingRatio};
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for (example, label) in [(1, 'a'), (1, 'b'), (1, 'b'), (2, 'a'), (3, 'c')] {
+    ///     bayesian.observe(example, label);
+    /// }
+    ///
+    /// assert_eq!(bayesian.label_ranking_for(1), vec!['b', 'a', 'c']);
+    /// assert_eq!(bayesian.label_ranking_with_scores(1)[0], ('b', CountingRatio::ratio(2, 3)));
+    /// ```
+    pub fn label_ranking_for(&self, example: S) -> Vec<L> {
+        self.label_ranking_with_scores(example)
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    /// Pairs every label with its posterior probability from `posterior_distribution()`,
+    /// ordered as in `label_ranking_for()`.
+    pub fn label_ranking_with_scores(&self, example: S) -> Vec<(L, CountingRatio)> {
+        let mut result = self.posterior_distribution(example);
+        // The sort is stable, so tied labels stay in ascending order.
+        result.sort_by(|(_, p1), (_, p2)| p2.cmp_value(p1));
+        result
+    }
+
+    /// Returns the label with the highest posterior probability given `example`, or `None`
+    /// if no labels have been observed. Ties are broken in favor of the smallest label.
+    ///
+    /// ```
+    /// use counting_ratio::BayesianCounter;
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for (example, label) in [(1, 'a'), (1, 'b'), (1, 'b'), (2, 'a'), (3, 'c')] {
+    ///     bayesian.observe(example, label);
+    /// }
+    ///
+    /// assert_eq!(bayesian.classify(1), Some('b'));
+    /// assert_eq!(bayesian.classify(3), Some('c'));
+    /// assert_eq!(bayesian.classify(4), Some('a'));
+    /// assert_eq!(BayesianCounter::<char, i32>::new().classify(1), None);
+    ///
+    /// // Example 1 is likelier under 'b', but 'a' is so much more common that it wins.
+    /// let mut bayesian = BayesianCounter::new();
+    /// for example in [1, 1, 1, 2, 2, 2, 2, 2, 2, 2] {
+    ///     bayesian.observe(example, 'a');
+    /// }
+    /// bayesian.observe(1, 'b');
+    /// bayesian.observe(2, 'b');
+    /// assert!(bayesian.p_example_given_label(1, 'a') < bayesian.p_example_given_label(1, 'b'));
+    /// assert_eq!(bayesian.classify(1), Some('a'));
+    /// assert_eq!(bayesian.label_ranking_for(1)[0], 'a');
+    /// ```
+    pub fn classify(&self, example: S) -> Option<L> {
+        let mut best: Option<(&L, (u128, u128))> = None;
+        for label in self.counts.keys() {
+            let score = self.score(&example, label);
+            if best.is_none_or(|(_, (n, d))| compare_fractions(score.0, score.1, n, d).is_gt()) {
+                best = Some((label, score));
+            }
+        }
+        best.map(|(label, _)| label.clone())
+    }
+
+    // The unnormalized posterior of `label`, p(example | label) * count(label), as a
+    // numerator and denominator.
+    fn score(&self, example: &S, label: &L) -> (u128, u128) {
+        let likelihood = self.p_example_given_label_of(example, label);
+        (
+            likelihood.matches() as u128 * self.label_count_of(label) as u128,
+            likelihood.observations() as u128,
+        )
+    }
+
+    /// Returns `p(label | example)` for every label, in ascending order of label. All of
+    /// the probabilities share a denominator, so they sum to exactly one; if `example` has
+    /// never been observed and there is no smoothing, they are all undefined.
+    ///
+    /// When smoothing makes the exact shared denominator too large for a `u64`, the
+    /// probabilities are rounded to a denominator near `u32::MAX`.
+    ///
+    /// ```
+    /// use counting_ratio::{BayesianCounter, CountingRatio};
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for (example, label) in [(1, 'a'), (1, 'b'), (1, 'b'), (2, 'a'), (3, 'c')] {
+    ///     bayesian.observe(example, label);
+    /// }
+    ///
+    /// assert_eq!(
+    ///     bayesian.posterior_distribution(1),
+    ///     vec![
+    ///         ('a', CountingRatio::ratio(1, 3)),
+    ///         ('b', CountingRatio::ratio(2, 3)),
+    ///         ('c', CountingRatio::ratio(0, 3)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn posterior_distribution(&self, example: S) -> Vec<(L, CountingRatio)> {
+        let scores: Vec<(u128, u128)> = self
+            .counts
+            .keys()
+            .map(|label| self.score(&example, label))
+            .collect();
+        let weights = exact_weights(&scores).unwrap_or_else(|| approximate_weights(&scores));
+        let total = weights.iter().sum();
+        self.counts
+            .keys()
+            .cloned()
+            .zip(weights)
+            .map(|(label, weight)| (label, CountingRatio::ratio(weight, total)))
+            .collect()
+    }
+}
+
+// Rewrites the fractions in `scores` over their least common denominator, then divides out
+// any common factor of the numerators. Returns `None` if the results do not fit in `u64`.
+fn exact_weights(scores: &[(u128, u128)]) -> Option<Vec<u64>> {
+    let mut denominator = 1u128;
+    for (_, d) in scores.iter() {
+        denominator = (denominator / gcd(denominator, *d)).checked_mul(*d)?;
+    }
+    let mut weights = Vec::new();
+    for (n, d) in scores.iter() {
+        weights.push(n.checked_mul(denominator / d)?);
+    }
+    let common = weights.iter().fold(0, |g, w| gcd(g, *w)).max(1);
+    let mut total = 0u64;
+    let mut result = Vec::new();
+    for weight in weights {
+        let weight = u64::try_from(weight / common).ok()?;
+        total = total.checked_add(weight)?;
+        result.push(weight);
+    }
+    Some(result)
+}
+
+fn approximate_weights(scores: &[(u128, u128)]) -> Vec<u64> {
+    let values: Vec<f64> = scores.iter().map(|(n, d)| *n as f64 / *d as f64).collect();
+    let scale = u32::MAX as f64 / values.iter().sum::<f64>();
+    values
+        .iter()
+        .map(|value| (value * scale + 0.5) as u64)
+        .collect()
+}
+
+impl<L: Countable, S: Countable> Default for BayesianCounter<L, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Countable, S: Countable> Add for BayesianCounter<L, S> {
+    type Output = BayesianCounter<L, S>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result += rhs;
+        result
+    }
+}
+
+impl<L: Countable, S: Countable> AddAssign for BayesianCounter<L, S> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.merge(&rhs);
+    }
+}
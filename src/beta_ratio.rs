@@ -0,0 +1,163 @@
+//! A `BetaRatio` tracks a proportion as a Beta(`alpha`, `beta`) distribution, the conjugate
+//! prior of a count of matches. Each observation adds one to `alpha` when the condition is
+//! met and one to `beta` when it is not, so the parameters are pseudo-counts of matches and
+//! misses. Unlike `PosteriorRatio`, which keeps the observed counts apart from its prior, a
+//! `BetaRatio` holds only the combined parameters, which need not be whole numbers.
+//!
+//! ```
+//! use counting_ratio::{BetaRatio, CountingRatio};
+//!
+//! let mut conversion = CountingRatio::ratio(3, 10).to_beta(1.0, 1.0);
+//! assert_eq!(conversion.parameters(), (4.0, 8.0));
+//!
+//! conversion.observe(true);
+//! assert_eq!(conversion.parameters(), (5.0, 8.0));
+//! assert_eq!(conversion.mean(), 5.0 / 13.0);
+//! assert_eq!(conversion.mode(), Some(4.0 / 11.0));
+//!
+//! assert_eq!(conversion.to_counts(1.0, 1.0), Some(CountingRatio::ratio(4, 11)));
+//! assert_eq!(conversion.to_counts(1.5, 1.0), None);
+//! assert_eq!(BetaRatio::new(1.0, 1.0).mode(), None);
+//! ```
+
+use crate::{CountingRatio, PosteriorRatio};
+use core::fmt::{Display, Formatter};
+use num_traits::float::FloatCore;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand_distr::{Beta, Distribution};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaRatio {
+    alpha: f64,
+    beta: f64,
+}
+
+impl CountingRatio {
+    /// The Beta distribution whose parameters add `alpha` pseudo-matches and `beta`
+    /// pseudo-misses to these counts. Both must be positive.
+    pub fn to_beta(self, alpha: f64, beta: f64) -> BetaRatio {
+        let (matches, observations) = self.counts_f64();
+        let mut result = BetaRatio::new(alpha, beta);
+        result.alpha += matches;
+        result.beta += observations - matches;
+        result
+    }
+}
+
+impl BetaRatio {
+    /// Both parameters must be positive; `(1.0, 1.0)` is the uniform distribution.
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        Self { alpha, beta }
+    }
+
+    pub fn observe(&mut self, condition_met: bool) {
+        if condition_met {
+            self.alpha += 1.0;
+        } else {
+            self.beta += 1.0;
+        }
+    }
+
+    pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
+        if prior_condition_met {
+            self.observe(posterior_condition_met);
+        }
+    }
+
+    pub fn parameters(&self) -> (f64, f64) {
+        (self.alpha, self.beta)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.alpha / (self.alpha + self.beta)
+    }
+
+    pub fn variance(&self) -> f64 {
+        let total = self.alpha + self.beta;
+        self.alpha * self.beta / (total * total * (total + 1.0))
+    }
+
+    /// The most probable proportion, or `None` when the density has no single peak: when
+    /// both parameters are one, or when both are below one.
+    pub fn mode(&self) -> Option<f64> {
+        match (self.alpha > 1.0, self.beta > 1.0) {
+            (true, true) => Some((self.alpha - 1.0) / (self.alpha + self.beta - 2.0)),
+            (true, false) => Some(1.0),
+            (false, true) => Some(0.0),
+            (false, false) if self.alpha < 1.0 && self.beta >= 1.0 => Some(0.0),
+            (false, false) if self.alpha >= 1.0 && self.beta < 1.0 => Some(1.0),
+            (false, false) => None,
+        }
+    }
+
+    /// Returns the equal-tailed interval containing the proportion with probability
+    /// `level`.
+    #[cfg(feature = "std")]
+    pub fn credible_interval(&self, level: f64) -> (f64, f64) {
+        let tail = (1.0 - level) / 2.0;
+        (
+            crate::special::beta_quantile(tail, self.alpha, self.beta),
+            crate::special::beta_quantile(1.0 - tail, self.alpha, self.beta),
+        )
+    }
+
+    /// Draws a proportion from the distribution. Requires the `rand` feature.
+    ///
+    /// ```
+    /// use counting_ratio::BetaRatio;
+    ///
+    /// let mut rng = rand::rng();
+    /// let sample = BetaRatio::new(40.0, 60.0).sample(&mut rng);
+    /// assert!((0.0..=1.0).contains(&sample));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        Beta::new(self.alpha, self.beta)
+            .expect("Beta parameters are positive")
+            .sample(rng)
+    }
+
+    /// The observed counts, after removing `alpha` pseudo-matches and `beta` pseudo-misses.
+    /// Returns `None` unless both remainders are whole numbers that fit in the counts.
+    pub fn to_counts(&self, alpha: f64, beta: f64) -> Option<CountingRatio> {
+        let matches = whole_count(self.alpha - alpha)?;
+        let misses = whole_count(self.beta - beta)?;
+        Some(CountingRatio::ratio(matches, matches.checked_add(misses)?))
+    }
+}
+
+impl From<PosteriorRatio> for BetaRatio {
+    fn from(posterior: PosteriorRatio) -> Self {
+        let (alpha, beta) = posterior.posterior_parameters();
+        Self { alpha, beta }
+    }
+}
+
+impl Display for BetaRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        write!(
+            f,
+            "Beta({}, {}) (mean {:.precision$}%)",
+            self.alpha,
+            self.beta,
+            100.0 * self.mean()
+        )
+    }
+}
+
+fn whole_count(x: f64) -> Option<u64> {
+    // 2^64 is the smallest float too large for a u64.
+    if (0.0..18_446_744_073_709_551_616.0).contains(&x) && FloatCore::fract(x) == 0.0 {
+        Some(x as u64)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,194 @@
+//! A `ChangePointDetector` watches a stream of observations for shifts in the underlying
+//! rate, using Bayesian online change-point detection with a Beta-Bernoulli model. It keeps
+//! a probability distribution over the run length, the number of observations since the
+//! most recent change, and flags a change whenever the most probable run length drops.
+//!
+//! ```
+//! use counting_ratio::ChangePointDetector;
+//!
+//! let mut defects = ChangePointDetector::new(0.01);
+//! let mut changes = Vec::new();
+//! for i in 0..400 {
+//!     // One defect in 20 at first, then one in 2.
+//!     let defective = if i < 200 { i % 20 == 0 } else { i % 2 == 0 };
+//!     if let Some(run_length) = defects.observe(defective) {
+//!         changes.push(i - run_length);
+//!     }
+//! }
+//!
+//! assert!(!changes.is_empty());
+//! assert!(changes.iter().all(|start| (190..=210).contains(start)));
+//! assert!((defects.estimate().unwrap() - 0.5).abs() < 0.1);
+//! ```
+
+use alloc::vec::Vec;
+
+// Run lengths whose probability falls below this are forgotten.
+const NEGLIGIBLE: f64 = 1e-9;
+
+// Unless configured otherwise, runs longer than this are merged into the longest shorter
+// run, so that a stationary stream does not accumulate one run length per observation.
+const DEFAULT_MAX_RUN_LENGTH: u64 = 1000;
+
+#[derive(Clone, Debug)]
+pub struct ChangePointDetector {
+    hazard: f64,
+    alpha: f64,
+    beta: f64,
+    // For each run length still tracked, in ascending order, its probability and the
+    // matches and observations in that run. The run length is its number of observations.
+    runs: Vec<(f64, u64, u64)>,
+    most_likely: usize,
+    max_run_length: u64,
+}
+
+impl ChangePointDetector {
+    /// `hazard` is the prior probability of a change before any given observation, which
+    /// must lie strictly between 0 and 1. Rates within each run have a uniform prior.
+    pub fn new(hazard: f64) -> Self {
+        Self::with_prior(hazard, 1.0, 1.0)
+    }
+
+    /// Uses a Beta(`alpha`, `beta`) prior for the rate within each run. Both parameters
+    /// must be positive.
+    pub fn with_prior(hazard: f64, alpha: f64, beta: f64) -> Self {
+        assert!(
+            hazard > 0.0 && hazard < 1.0,
+            "hazard must lie strictly between 0 and 1"
+        );
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        Self {
+            hazard,
+            alpha,
+            beta,
+            runs: alloc::vec![(1.0, 0, 0)],
+            most_likely: 0,
+            max_run_length: DEFAULT_MAX_RUN_LENGTH,
+        }
+    }
+
+    /// Tracks run lengths up to `max_run_length`, which must be positive, merging the
+    /// probability of any longer run into the longest run kept. Memory and time per
+    /// observation grow with this bound, which defaults to 1000. Estimates then reflect
+    /// at most the latest `max_run_length` observations.
+    ///
+    /// ```
+    /// use counting_ratio::ChangePointDetector;
+    ///
+    /// let mut defects = ChangePointDetector::new(0.01).with_max_run_length(100);
+    /// for i in 0..5000 {
+    ///     defects.observe(i % 10 == 0);
+    /// }
+    /// assert_eq!(defects.most_likely_run_length(), 100);
+    /// assert_eq!(defects.run_length_probability(101), 0.0);
+    /// assert!((defects.estimate().unwrap() - 0.1).abs() < 0.01);
+    /// ```
+    pub fn with_max_run_length(self, max_run_length: u64) -> Self {
+        assert!(max_run_length > 0, "max_run_length must be positive");
+        Self {
+            max_run_length,
+            ..self
+        }
+    }
+
+    pub fn max_run_length(&self) -> u64 {
+        self.max_run_length
+    }
+
+    /// Observes the next outcome. If the most probable run length dropped, a change is
+    /// flagged by returning the new most probable run length, the number of observations
+    /// believed to have followed the change.
+    pub fn observe(&mut self, condition_met: bool) -> Option<usize> {
+        let mut next = Vec::with_capacity(self.runs.len() + 1);
+        next.push((0.0, 0, 0));
+        let mut total = 0.0;
+        for (p, matches, observations) in self.runs.iter() {
+            let p_match =
+                (self.alpha + *matches as f64) / (self.alpha + self.beta + *observations as f64);
+            let likelihood = if condition_met {
+                p_match
+            } else {
+                1.0 - p_match
+            };
+            let p = p * likelihood;
+            next[0].0 += p * self.hazard;
+            next.push((
+                p * (1.0 - self.hazard),
+                matches + condition_met as u64,
+                observations + 1,
+            ));
+            total += p;
+        }
+        for run in next.iter_mut() {
+            run.0 /= total;
+        }
+        // Runs only grow by one observation at a time, so at most the last exceeds the bound.
+        if next.last().is_some_and(|run| run.2 > self.max_run_length) {
+            let (p, _, _) = next.pop().expect("the last run exists");
+            if let Some(longest) = next.last_mut() {
+                longest.0 += p;
+            }
+        }
+        // The run that starts afresh is always kept, however small the hazard.
+        next.retain(|run| run.2 == 0 || run.0 >= NEGLIGIBLE);
+        self.runs = next;
+
+        let previous = self.most_likely;
+        self.most_likely = self.most_likely_run_length();
+        if self.most_likely < previous {
+            Some(self.most_likely)
+        } else {
+            None
+        }
+    }
+
+    pub fn observe_with_prior(
+        &mut self,
+        prior_condition_met: bool,
+        posterior_condition_met: bool,
+    ) -> Option<usize> {
+        if prior_condition_met {
+            self.observe(posterior_condition_met)
+        } else {
+            None
+        }
+    }
+
+    /// The number of observations since the most probable most recent change.
+    pub fn most_likely_run_length(&self) -> usize {
+        self.most_likely_run().2 as usize
+    }
+
+    fn most_likely_run(&self) -> (f64, u64, u64) {
+        let mut best = self.runs[0];
+        for run in self.runs.iter() {
+            if run.0 > best.0 {
+                best = *run;
+            }
+        }
+        best
+    }
+
+    /// The probability that exactly `run_length` observations have followed the most
+    /// recent change.
+    pub fn run_length_probability(&self, run_length: usize) -> f64 {
+        self.runs
+            .iter()
+            .find(|run| run.2 == run_length as u64)
+            .map_or(0.0, |run| run.0)
+    }
+
+    /// The rate observed since the most probable most recent change, or `None` if no
+    /// observations have followed it.
+    pub fn estimate(&self) -> Option<f64> {
+        let (_, matches, observations) = self.most_likely_run();
+        if observations == 0 {
+            None
+        } else {
+            Some(matches as f64 / observations as f64)
+        }
+    }
+}
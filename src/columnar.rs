@@ -0,0 +1,123 @@
+//! Conversion of counts into Apache Arrow `RecordBatch`es, so that they can flow into
+//! columnar tools such as DataFusion or pandas. Labels, examples, and keys become string
+//! columns through their `Display` implementations. Requires the `arrow` feature; the
+//! `parquet` feature adds `write_parquet()`.
+//!
+//! ```
+//! use counting_ratio::{BayesianCounter, CountingRatio, GroupedRatio};
+//! use counting_ratio::columnar::ratios_to_record_batch;
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("hello", "ham");
+//! let batch = bayesian.to_record_batch().unwrap();
+//! assert_eq!(batch.num_rows(), 2);
+//! assert_eq!(batch.schema().field(2).name(), "count");
+//!
+//! let mut suites = GroupedRatio::new();
+//! suites.add("unit", CountingRatio::ratio(2, 3));
+//! suites.add("integration", CountingRatio::new());
+//! let batch = suites.to_record_batch().unwrap();
+//! assert_eq!(batch.num_columns(), 4);
+//! assert_eq!(batch.column(3).null_count(), 1);
+//!
+//! let batch = ratios_to_record_batch([CountingRatio::ratio(1, 4), CountingRatio::ratio(3, 4)]).unwrap();
+//! assert_eq!(batch.num_rows(), 2);
+//! ```
+
+use crate::{BayesianCounter, Countable, CountingRatio, GroupedRatio};
+use arrow::array::{ArrayRef, Float64Array, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use std::fmt::Display;
+use std::sync::Arc;
+
+impl<L: Countable + Display, S: Countable + Display> BayesianCounter<L, S> {
+    /// One row of `label`, `example`, and `count` for every nonzero count.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let (mut labels, mut examples, mut counts) = (Vec::new(), Vec::new(), Vec::new());
+        for (label, example, count) in self.nonzero_counts() {
+            labels.push(label.to_string());
+            examples.push(example.to_string());
+            counts.push(count);
+        }
+        let schema = Schema::new(vec![
+            Field::new("label", DataType::Utf8, false),
+            Field::new("example", DataType::Utf8, false),
+            Field::new("count", DataType::UInt64, false),
+        ]);
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(labels)),
+            Arc::new(StringArray::from(examples)),
+            Arc::new(UInt64Array::from(counts)),
+        ];
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+}
+
+impl<K: Ord + Display> GroupedRatio<K> {
+    /// One row of `key`, `matches`, `observations`, and `rate` for every key. The rate is
+    /// null for undefined ratios.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let keys: Vec<String> = self.keys().map(|key| key.to_string()).collect();
+        let mut fields = vec![Field::new("key", DataType::Utf8, false)];
+        fields.extend(ratio_fields());
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(StringArray::from(keys))];
+        columns.extend(ratio_columns(self.iter().map(|(_, ratio)| ratio)));
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+}
+
+/// One row of `matches`, `observations`, and `rate` for every ratio. The rate is null for
+/// undefined ratios.
+pub fn ratios_to_record_batch<I: IntoIterator<Item = CountingRatio>>(
+    ratios: I,
+) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_new(Arc::new(Schema::new(ratio_fields())), ratio_columns(ratios))
+}
+
+/// Writes `batch` to `writer` as a Parquet file. Requires the `parquet` feature.
+///
+/// ```
+/// use counting_ratio::CountingRatio;
+/// use counting_ratio::columnar::{ratios_to_record_batch, write_parquet};
+///
+/// let batch = ratios_to_record_batch([CountingRatio::ratio(1, 4)]).unwrap();
+/// let mut file = Vec::new();
+/// write_parquet(&batch, &mut file).unwrap();
+/// assert!(file.starts_with(b"PAR1") && file.ends_with(b"PAR1"));
+/// ```
+#[cfg(feature = "parquet")]
+pub fn write_parquet<W: std::io::Write + Send>(
+    batch: &RecordBatch,
+    writer: W,
+) -> Result<(), parquet::errors::ParquetError> {
+    let mut writer = parquet::arrow::ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+fn ratio_fields() -> Vec<Field> {
+    vec![
+        Field::new("matches", DataType::UInt64, false),
+        Field::new("observations", DataType::UInt64, false),
+        Field::new("rate", DataType::Float64, true),
+    ]
+}
+
+fn ratio_columns<I: IntoIterator<Item = CountingRatio>>(ratios: I) -> Vec<ArrayRef> {
+    let (mut matches, mut observations, mut rates) = (Vec::new(), Vec::new(), Vec::new());
+    for ratio in ratios {
+        matches.push(ratio.matches());
+        observations.push(ratio.observations());
+        rates.push(ratio.value());
+    }
+    vec![
+        Arc::new(UInt64Array::from(matches)),
+        Arc::new(UInt64Array::from(observations)),
+        Arc::new(Float64Array::from(rates)),
+    ]
+}
@@ -0,0 +1,90 @@
+//! A `ConcurrentBayesianCounter` accepts observations from many threads at once. It spreads
+//! them across several independently locked shards, so that threads rarely wait on one
+//! another, and merges the shards into an ordinary `BayesianCounter` on demand.
+//!
+//! ```
+//! use counting_ratio::{ConcurrentBayesianCounter, CountingRatio};
+//! use std::thread;
+//!
+//! let counter = ConcurrentBayesianCounter::new();
+//! thread::scope(|scope| {
+//!     for worker in 0..4 {
+//!         let counter = &counter;
+//!         scope.spawn(move || {
+//!             for i in 0..100 {
+//!                 counter.observe(i % 10, worker % 2 == 0);
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! let snapshot = counter.snapshot();
+//! assert_eq!(snapshot.total(), 400);
+//! assert_eq!(snapshot.p_label(true), CountingRatio::ratio(200, 400));
+//! assert_eq!(snapshot.count(3, false), 20);
+//! ```
+
+use crate::{BayesianCounter, Countable};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+pub struct ConcurrentBayesianCounter<L: Countable, S: Countable> {
+    shards: Vec<Mutex<BayesianCounter<L, S>>>,
+}
+
+impl<L: Countable, S: Countable> ConcurrentBayesianCounter<L, S> {
+    /// Creates one shard for each thread the system can run in parallel.
+    pub fn new() -> Self {
+        Self::with_shards(
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        )
+    }
+
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(
+            shards > 0,
+            "ConcurrentBayesianCounter needs at least one shard"
+        );
+        Self {
+            shards: (0..shards)
+                .map(|_| Mutex::new(BayesianCounter::new()))
+                .collect(),
+        }
+    }
+
+    // Each thread consistently uses the same shard.
+    fn shard(&self) -> &Mutex<BayesianCounter<L, S>> {
+        let mut hasher = DefaultHasher::new();
+        std::thread::current().id().hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    pub fn observe(&self, example: S, label: L) {
+        self.shard()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .observe(example, label);
+    }
+
+    /// Merges every shard into a single `BayesianCounter`.
+    pub fn snapshot(&self) -> BayesianCounter<L, S> {
+        let mut result = BayesianCounter::new();
+        for shard in self.shards.iter() {
+            result.merge(
+                &shard
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            );
+        }
+        result
+    }
+}
+
+impl<L: Countable, S: Countable> Default for ConcurrentBayesianCounter<L, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,135 @@
+//! Recovering counts from a proportion reported as a float, through the continued fraction
+//! of its exact binary value. `CountingRatio::approximate()` finds the closest ratio with a
+//! bounded number of observations, while `TryFrom<f64>` finds a ratio with few observations
+//! that converts back to exactly the same float.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! assert_eq!(CountingRatio::approximate(0.3333, 100), Some(CountingRatio::ratio(1, 3)));
+//! assert_eq!(
+//!     CountingRatio::approximate(std::f64::consts::PI - 3.0, 1000),
+//!     Some(CountingRatio::ratio(16, 113))
+//! );
+//! assert_eq!(CountingRatio::approximate(1.5, 10), None);
+//!
+//! assert_eq!(CountingRatio::try_from(0.1), Ok(CountingRatio::ratio(1, 10)));
+//! assert_eq!(CountingRatio::try_from(2.0 / 3.0), Ok(CountingRatio::ratio(2, 3)));
+//! assert_eq!(CountingRatio::try_from(-0.5), Err(-0.5));
+//! ```
+
+use crate::{compare_fractions, CountingRatio};
+use core::cmp::Ordering;
+use num_traits::float::FloatCore;
+
+impl CountingRatio {
+    /// The ratio with at most `max_denominator` observations whose value lies closest to
+    /// `value`, preferring fewer observations on a tie. Returns `None` unless `value` lies
+    /// within `[0.0, 1.0]`. Panics if `max_denominator` is zero.
+    pub fn approximate(value: f64, max_denominator: u64) -> Option<Self> {
+        assert!(max_denominator > 0, "max_denominator must be positive");
+        let max_denominator = u128::from(max_denominator);
+        let mut fraction = ContinuedFraction::new(value)?;
+        while fraction.step(max_denominator) {}
+        Some(fraction.closest(max_denominator))
+    }
+}
+
+/// Fails for a value outside `[0.0, 1.0]`, or one that no ratio with `u64` counts converts
+/// back to, returning it unchanged. Otherwise yields the first convergent of the continued
+/// fraction of `value` that converts back to exactly `value`.
+impl TryFrom<f64> for CountingRatio {
+    type Error = f64;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        let mut fraction = ContinuedFraction::new(value).ok_or(value)?;
+        while fraction.step(u128::from(u64::MAX)) {
+            let ratio = fraction.convergent();
+            if f64::from(ratio) == value {
+                return Ok(ratio);
+            }
+        }
+        Err(value)
+    }
+}
+
+// The expansion of a value within [0.0, 1.0] into a continued fraction, one term per step.
+// The value equals (p1 * t + p0) / (q1 * t + q0), where t = n / d is the complete quotient
+// still to be expanded.
+struct ContinuedFraction {
+    n: u128,
+    d: u128,
+    p0: u128,
+    q0: u128,
+    p1: u128,
+    q1: u128,
+}
+
+impl ContinuedFraction {
+    fn new(value: f64) -> Option<Self> {
+        if !(0.0..=1.0).contains(&value) {
+            return None;
+        }
+        let (mantissa, exponent, _) = FloatCore::integer_decode(value);
+        let zeros = mantissa.trailing_zeros().min(63);
+        let shift = -(i32::from(exponent) + zeros as i32);
+        // Below 2^-75, no denominator that fits in a u64 comes closer than zero.
+        let (n, d) = if mantissa == 0 || shift > 127 {
+            (0, 1)
+        } else {
+            (u128::from(mantissa >> zeros), 1 << shift)
+        };
+        Some(Self {
+            n,
+            d,
+            p0: 0,
+            q0: 1,
+            p1: 1,
+            q1: 0,
+        })
+    }
+
+    // Advances to the next convergent, unless the expansion is complete or the next
+    // convergent's denominator would exceed `max_denominator`.
+    fn step(&mut self, max_denominator: u128) -> bool {
+        if self.d == 0 {
+            return false;
+        }
+        let term = self.n / self.d;
+        match term
+            .checked_mul(self.q1)
+            .and_then(|q| q.checked_add(self.q0))
+        {
+            Some(q2) if q2 <= max_denominator => {
+                let p2 = term * self.p1 + self.p0;
+                (self.p0, self.q0, self.p1, self.q1) = (self.p1, self.q1, p2, q2);
+                (self.n, self.d) = (self.d, self.n - term * self.d);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn convergent(&self) -> CountingRatio {
+        CountingRatio::ratio(self.p1 as u64, self.q1 as u64)
+    }
+
+    // The closest ratio to the value with at most `max_denominator` observations, once
+    // `step()` has stopped: either the last convergent or the semiconvergent that adds as
+    // many copies of it to the one before as the bound permits. The semiconvergent lies
+    // strictly closer exactly when t < (q0 + 2k * q1) / q1.
+    fn closest(&self, max_denominator: u128) -> CountingRatio {
+        if self.d == 0 {
+            return self.convergent();
+        }
+        let k = (max_denominator - self.q0) / self.q1;
+        let semiconvergent_closer =
+            compare_fractions(self.n, self.d, self.q0 + 2 * k * self.q1, self.q1) == Ordering::Less;
+        if semiconvergent_closer {
+            let (p, q) = (self.p0 + k * self.p1, self.q0 + k * self.q1);
+            CountingRatio::ratio(p as u64, q as u64)
+        } else {
+            self.convergent()
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Reading and writing counts as CSV, for analysis in spreadsheets. A `BayesianCounter` is
+//! written as rows of `label,example,count`, and a `GroupedRatio` as rows of
+//! `key,matches,observations`, each after a header row. Requires the `csv` feature.
+//!
+//! ```
+//! use counting_ratio::{BayesianCounter, CountingRatio, GroupedRatio};
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("hello", "ham");
+//!
+//! let mut csv = Vec::new();
+//! bayesian.write_csv(&mut csv).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(csv.clone()).unwrap(),
+//!     "label,example,count\nham,hello,1\nspam,buy,2\n"
+//! );
+//! let restored: BayesianCounter<String, String> = BayesianCounter::read_csv(csv.as_slice()).unwrap();
+//! assert_eq!(restored.count("buy".to_string(), "spam".to_string()), 2);
+//!
+//! let mut suites = GroupedRatio::new();
+//! suites.add("unit".to_string(), CountingRatio::ratio(2, 3));
+//! let mut csv = Vec::new();
+//! suites.write_csv(&mut csv).unwrap();
+//! assert_eq!(String::from_utf8(csv.clone()).unwrap(), "key,matches,observations\nunit,2,3\n");
+//! assert_eq!(GroupedRatio::read_csv(csv.as_slice()).unwrap(), suites);
+//! ```
+
+use crate::{BayesianCounter, Countable, CountingRatio, GroupedRatio};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Read, Write};
+
+impl<L: Countable + Serialize, S: Countable + Serialize> BayesianCounter<L, S> {
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["label", "example", "count"])?;
+        for row in self.nonzero_counts() {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<L: Countable + DeserializeOwned, S: Countable + DeserializeOwned> BayesianCounter<L, S> {
+    /// Reads a counter from rows written by `write_csv()`. The result has no smoothing.
+    pub fn read_csv<R: Read>(reader: R) -> Result<Self, csv::Error> {
+        let mut result = Self::new();
+        for row in csv::Reader::from_reader(reader).into_deserialize() {
+            let (label, example, count): (L, S, u64) = row?;
+            result.observe_count(example, label, count);
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Ord + Serialize> GroupedRatio<K> {
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["key", "matches", "observations"])?;
+        for (key, ratio) in self.iter() {
+            writer.serialize((key, ratio.matches(), ratio.observations()))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<K: Ord + DeserializeOwned> GroupedRatio<K> {
+    /// Reads ratios from rows written by `write_csv()`. Rows that repeat a key are added
+    /// together.
+    pub fn read_csv<R: Read>(reader: R) -> Result<Self, csv::Error> {
+        let mut result = Self::new();
+        for row in csv::Reader::from_reader(reader).into_deserialize() {
+            let (key, matches, observations): (K, u64, u64) = row?;
+            result.add(key, CountingRatio::ratio(matches, observations));
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,115 @@
+//! A `CusumChart` is a tabular CUSUM control chart for a proportion, as used in statistical
+//! process control. It accumulates how far each observation strays beyond a slack around
+//! the target proportion, once for upward and once for downward drift, and signals that the
+//! process is out of control once either sum exceeds the decision interval.
+//!
+//! ```
+//! use counting_ratio::{ControlSignal, CusumChart};
+//!
+//! // The target defect rate is 10%, and shifts of more than 5% should be caught.
+//! let mut defects = CusumChart::new(0.1, 0.05, 2.0);
+//! for i in 0..100 {
+//!     assert_eq!(defects.observe(i % 10 == 0), None);
+//! }
+//!
+//! let mut signals = Vec::new();
+//! for i in 0..10 {
+//!     signals.push(defects.observe(i % 2 == 0));
+//! }
+//! assert_eq!(signals[5], Some(ControlSignal::AboveUpperLimit));
+//! assert!(defects.upper_sum() > 2.0);
+//!
+//! defects.reset();
+//! assert_eq!(defects.upper_sum(), 0.0);
+//! ```
+
+/// Which way a control chart saw the process drift.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlSignal {
+    AboveUpperLimit,
+    BelowLowerLimit,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CusumChart {
+    target: f64,
+    slack: f64,
+    decision_interval: f64,
+    upper: f64,
+    lower: f64,
+}
+
+impl CusumChart {
+    /// `target` is the in-control proportion, which must lie within `[0.0, 1.0]`. Drift of
+    /// up to `slack` away from it is tolerated, and an out-of-control signal is given once
+    /// the accumulated drift exceeds `decision_interval`. Neither may be negative.
+    pub fn new(target: f64, slack: f64, decision_interval: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&target),
+            "CusumChart target must lie within [0.0, 1.0]"
+        );
+        assert!(slack >= 0.0, "CusumChart slack must not be negative");
+        assert!(
+            decision_interval >= 0.0,
+            "CusumChart decision interval must not be negative"
+        );
+        Self {
+            target,
+            slack,
+            decision_interval,
+            upper: 0.0,
+            lower: 0.0,
+        }
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn slack(&self) -> f64 {
+        self.slack
+    }
+
+    pub fn decision_interval(&self) -> f64 {
+        self.decision_interval
+    }
+
+    /// Accumulates one observation, returning a signal while the process is out of
+    /// control. An upward signal takes precedence if both sums exceed the decision
+    /// interval.
+    pub fn observe(&mut self, condition_met: bool) -> Option<ControlSignal> {
+        let x = if condition_met { 1.0 } else { 0.0 };
+        self.upper = (self.upper + x - (self.target + self.slack)).max(0.0);
+        self.lower = (self.lower + (self.target - self.slack) - x).max(0.0);
+        self.signal()
+    }
+
+    /// The current signal, if either sum exceeds the decision interval.
+    pub fn signal(&self) -> Option<ControlSignal> {
+        if self.upper > self.decision_interval {
+            Some(ControlSignal::AboveUpperLimit)
+        } else if self.lower > self.decision_interval {
+            Some(ControlSignal::BelowLowerLimit)
+        } else {
+            None
+        }
+    }
+
+    /// The accumulated upward drift beyond the slack.
+    pub fn upper_sum(&self) -> f64 {
+        self.upper
+    }
+
+    /// The accumulated downward drift beyond the slack.
+    pub fn lower_sum(&self) -> f64 {
+        self.lower
+    }
+
+    /// Restarts both sums, as after the cause of a signal has been corrected.
+    pub fn reset(&mut self) {
+        self.upper = 0.0;
+        self.lower = 0.0;
+    }
+}
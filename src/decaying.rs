@@ -0,0 +1,106 @@
+//! A `DecayingRatio` weights recent observations more heavily than older ones. Each new
+//! observation multiplies the weight of all prior observations by `1 - alpha`, so no
+//! window of past observations needs to be stored.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use counting_ratio::DecayingRatio;
+//!
+//! let mut recent = DecayingRatio::with_half_life(2.0);
+//! for _ in 0..20 {
+//!     recent.observe(false);
+//! }
+//! for _ in 0..4 {
+//!     recent.observe(true);
+//! }
+//!
+//! assert!(recent.estimate().unwrap() > 0.7);
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use core::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecayingRatio {
+    alpha: f64,
+    matches: f64,
+    observations: f64,
+}
+
+impl DecayingRatio {
+    /// `alpha` is the fraction of weight lost by every prior observation whenever a new
+    /// observation arrives. It must lie within `(0.0, 1.0]`.
+    pub fn with_alpha(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "DecayingRatio alpha must lie within (0.0, 1.0]"
+        );
+        Self {
+            alpha,
+            matches: 0.0,
+            observations: 0.0,
+        }
+    }
+
+    /// After `half_life` further observations, an observation carries half of its original weight.
+    #[cfg(feature = "std")]
+    pub fn with_half_life(half_life: f64) -> Self {
+        assert!(half_life > 0.0, "DecayingRatio half-life must be positive");
+        Self::with_alpha(1.0 - 0.5_f64.powf(1.0 / half_life))
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn observe(&mut self, condition_met: bool) {
+        let retained = 1.0 - self.alpha;
+        self.matches *= retained;
+        self.observations *= retained;
+        self.observations += 1.0;
+        if condition_met {
+            self.matches += 1.0;
+        }
+    }
+
+    pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
+        if prior_condition_met {
+            self.observe(posterior_condition_met);
+        }
+    }
+
+    pub fn defined(&self) -> bool {
+        self.observations > 0.0
+    }
+
+    pub fn weighted_matches(&self) -> f64 {
+        self.matches
+    }
+
+    pub fn weighted_observations(&self) -> f64 {
+        self.observations
+    }
+
+    pub fn estimate(&self) -> Option<f64> {
+        if self.defined() {
+            Some(self.matches / self.observations)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for DecayingRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:.2}/{:.2} ({:.2}%)",
+            self.matches,
+            self.observations,
+            100.0 * self.estimate().unwrap_or(f64::NAN)
+        )
+    }
+}
@@ -0,0 +1,155 @@
+//! A `DirichletPosterior` combines the counts of an `OutcomeCounter` with a Dirichlet prior,
+//! the categorical counterpart of the Beta prior of `PosteriorRatio`. The prior gives each
+//! outcome a positive weight of pseudo-observations, so that small samples still yield
+//! sensible estimates. Outcomes observed without being named in the prior receive no prior
+//! weight.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use counting_ratio::OutcomeCounter;
+//!
+//! let rolls: OutcomeCounter<u8> = [1, 1, 2, 6].into_iter().collect();
+//! let posterior = rolls.posterior((1..=6).map(|face| (face, 1.0)));
+//!
+//! assert_eq!(posterior.posterior_mean(&1), Some(0.3));
+//! assert_eq!(posterior.posterior_mean(&3), Some(0.1));
+//! assert_eq!(posterior.posterior_mean(&7), None);
+//!
+//! let (low, high) = posterior.credible_interval(&1, 0.95).unwrap();
+//! assert!(low < 0.3 && 0.3 < high);
+//!
+//! // The chance that the next two rolls are a one and a three, in either order.
+//! let next: OutcomeCounter<u8> = [1, 3].into_iter().collect();
+//! let expected = 2.0 * 3.0 * 1.0 / (10.0 * 11.0);
+//! assert!((posterior.predictive_probability(&next) - expected).abs() < 1e-12);
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use crate::{Countable, OutcomeCounter};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirichletPosterior<E: Countable> {
+    counts: OutcomeCounter<E>,
+    prior: BTreeMap<E, f64>,
+}
+
+impl<E: Countable> OutcomeCounter<E> {
+    /// Starts counting from a Dirichlet prior that gives each outcome the paired weight.
+    /// Every weight must be positive; a weight of 1.0 for every outcome is the uniform prior.
+    pub fn with_prior<I: IntoIterator<Item = (E, f64)>>(prior: I) -> DirichletPosterior<E> {
+        OutcomeCounter::new().posterior(prior)
+    }
+
+    /// Combines the counts observed so far with a Dirichlet prior that gives each outcome
+    /// the paired weight. Every weight must be positive.
+    pub fn posterior<I: IntoIterator<Item = (E, f64)>>(self, prior: I) -> DirichletPosterior<E> {
+        let mut weights = BTreeMap::new();
+        for (outcome, weight) in prior {
+            assert!(weight > 0.0, "Dirichlet prior weights must be positive");
+            *weights.entry(outcome).or_default() += weight;
+        }
+        DirichletPosterior {
+            counts: self,
+            prior: weights,
+        }
+    }
+}
+
+impl<E: Countable> DirichletPosterior<E> {
+    pub fn observe(&mut self, outcome: E) {
+        self.counts.observe(outcome);
+    }
+
+    /// The observed counts, without any contribution from the prior.
+    pub fn counts(&self) -> &OutcomeCounter<E> {
+        &self.counts
+    }
+
+    /// The prior weight of `outcome`, which is zero unless the prior names it.
+    pub fn prior(&self, outcome: &E) -> f64 {
+        self.prior.get(outcome).copied().unwrap_or_default()
+    }
+
+    /// The parameters of the Dirichlet posterior distribution for every outcome named in
+    /// the prior or observed, in ascending order of outcome.
+    pub fn posterior_parameters(&self) -> Vec<(&E, f64)> {
+        let mut parameters: BTreeMap<&E, f64> = BTreeMap::new();
+        for (outcome, weight) in self.prior.iter() {
+            *parameters.entry(outcome).or_default() += weight;
+        }
+        for (outcome, count) in self.counts.iter() {
+            *parameters.entry(outcome).or_default() += count as f64;
+        }
+        parameters.into_iter().collect()
+    }
+
+    /// The posterior expected share of `outcome`, which is also the probability that the
+    /// next observation is `outcome`. Returns `None` for an outcome neither named in the
+    /// prior nor observed.
+    pub fn posterior_mean(&self, outcome: &E) -> Option<f64> {
+        let (parameter, total) = self.marginal(outcome)?;
+        Some(parameter / total)
+    }
+
+    /// Returns the equal-tailed interval containing the share of `outcome` with
+    /// probability `level` under the posterior distribution, or `None` for an outcome
+    /// neither named in the prior nor observed.
+    #[cfg(feature = "std")]
+    pub fn credible_interval(&self, outcome: &E, level: f64) -> Option<(f64, f64)> {
+        let (parameter, total) = self.marginal(outcome)?;
+        let rest = total - parameter;
+        if rest <= 0.0 {
+            // No other outcome is possible.
+            return Some((1.0, 1.0));
+        }
+        let tail = (1.0 - level) / 2.0;
+        Some((
+            crate::special::beta_quantile(tail, parameter, rest),
+            crate::special::beta_quantile(1.0 - tail, parameter, rest),
+        ))
+    }
+
+    /// The probability under the Dirichlet-multinomial posterior predictive distribution
+    /// that as many further observations as `next` holds have exactly its counts, in any
+    /// order. An outcome neither named in the prior nor observed has probability zero.
+    #[cfg(feature = "std")]
+    pub fn predictive_probability(&self, next: &OutcomeCounter<E>) -> f64 {
+        use crate::special::ln_gamma;
+
+        let parameters = self.posterior_parameters();
+        let total: f64 = parameters.iter().map(|(_, parameter)| parameter).sum();
+        let n = next.total() as f64;
+        let mut ln_p = ln_gamma(n + 1.0) + ln_gamma(total) - ln_gamma(n + total);
+        for (outcome, count) in next.iter() {
+            let Some(parameter) = self.marginal(outcome).map(|(parameter, _)| parameter) else {
+                return 0.0;
+            };
+            let count = count as f64;
+            ln_p += ln_gamma(count + parameter) - ln_gamma(parameter) - ln_gamma(count + 1.0);
+        }
+        ln_p.exp()
+    }
+
+    // The posterior parameter of `outcome` and the sum of all parameters.
+    fn marginal(&self, outcome: &E) -> Option<(f64, f64)> {
+        let parameter = self.prior(outcome) + self.counts.count(outcome) as f64;
+        if parameter > 0.0 {
+            let prior_total: f64 = self.prior.values().sum();
+            Some((parameter, prior_total + self.counts.total() as f64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<E: Countable> From<DirichletPosterior<E>> for OutcomeCounter<E> {
+    fn from(posterior: DirichletPosterior<E>) -> Self {
+        posterior.counts
+    }
+}
@@ -0,0 +1,105 @@
+//! An `EValueTest` tests whether a stream of observations has the proportion `p0`, in a way
+//! that remains valid however often it is checked. It maintains an e-process: the
+//! likelihood of the observations averaged over a Beta prior on the alternative, divided by
+//! their likelihood under `p0`, which is the `bayes_factor()` of the observations so far.
+//! Under the null hypothesis the e-process exceeds `1 / alpha` with probability at most
+//! `alpha`, ever, so the test may be monitored after every observation and stopped as soon
+//! as it rejects.
+//!
+//! ```
+//! use counting_ratio::EValueTest;
+//!
+//! let mut fair = EValueTest::new(0.5);
+//! for i in 0..1000 {
+//!     fair.observe(i % 2 == 0);
+//! }
+//! assert!(!fair.rejects(0.05));
+//!
+//! let mut biased = EValueTest::new(0.5);
+//! let stopped_at = (0..1000).position(|i| {
+//!     biased.observe(i % 10 < 7);
+//!     biased.rejects(0.05)
+//! });
+//! assert!(stopped_at.is_some_and(|n| n < 100));
+//! assert!(biased.e_value() >= 20.0);
+//! ```
+
+use crate::CountingRatio;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EValueTest {
+    null: f64,
+    alpha: f64,
+    beta: f64,
+    counts: CountingRatio,
+    max_ln_e_value: f64,
+}
+
+impl EValueTest {
+    /// Tests the null hypothesis that the proportion is `null`, which must lie strictly
+    /// between 0 and 1, against a uniform prior on the alternative.
+    pub fn new(null: f64) -> Self {
+        Self::with_prior(null, 1.0, 1.0)
+    }
+
+    /// Averages the alternative over a Beta(`alpha`, `beta`) prior. Both parameters must be
+    /// positive. Concentrating the prior where a deviation is expected makes the test
+    /// quicker to reject when the expectation holds.
+    pub fn with_prior(null: f64, alpha: f64, beta: f64) -> Self {
+        assert!(
+            null > 0.0 && null < 1.0,
+            "null proportion must lie strictly between 0 and 1"
+        );
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        Self {
+            null,
+            alpha,
+            beta,
+            counts: CountingRatio::new(),
+            max_ln_e_value: 0.0,
+        }
+    }
+
+    pub fn null(&self) -> f64 {
+        self.null
+    }
+
+    pub fn counts(&self) -> CountingRatio {
+        self.counts
+    }
+
+    pub fn observe(&mut self, condition_met: bool) {
+        self.counts.observe(condition_met);
+        self.max_ln_e_value = self.max_ln_e_value.max(self.ln_e_value());
+    }
+
+    /// The natural logarithm of `e_value()`.
+    pub fn ln_e_value(&self) -> f64 {
+        self.counts
+            .ln_bayes_factor(self.null, self.alpha, self.beta)
+    }
+
+    /// The current evidence against the null hypothesis, which starts at 1.
+    pub fn e_value(&self) -> f64 {
+        self.ln_e_value().exp()
+    }
+
+    /// The largest e-value reached after any observation so far.
+    pub fn max_e_value(&self) -> f64 {
+        self.max_ln_e_value.exp()
+    }
+
+    /// Whether the null hypothesis is rejected at significance level `alpha`, because the
+    /// e-value has reached `1 / alpha` at some point. Once rejected, it stays rejected.
+    pub fn rejects(&self, alpha: f64) -> bool {
+        assert!(
+            alpha > 0.0 && alpha < 1.0,
+            "significance level must lie strictly between 0 and 1"
+        );
+        self.max_ln_e_value >= -alpha.ln()
+    }
+}
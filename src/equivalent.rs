@@ -0,0 +1,74 @@
+//! Equality of `CountingRatio`s is structural: 3/10 and 6/20 differ, since they record
+//! different observations. `Equivalent` wraps a ratio so that equality, hashing, and
+//! ordering follow its mathematical value instead, as when deduplicating ratios in a set or
+//! using them as map keys.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, Equivalent};
+//! use std::collections::HashSet;
+//!
+//! let a = CountingRatio::ratio(3, 10);
+//! let b = CountingRatio::ratio(6, 20);
+//! assert_ne!(a, b);
+//! assert!(a.equivalent_to(&b));
+//! assert_eq!(Equivalent(a), Equivalent(b));
+//!
+//! let distinct: HashSet<_> = [a, b, CountingRatio::ratio(1, 2), CountingRatio::ratio(5, 10)]
+//!     .into_iter()
+//!     .map(Equivalent)
+//!     .collect();
+//! assert_eq!(distinct.len(), 2);
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equivalent<T: Count = u64>(pub GenericCountingRatio<T>);
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// Whether both ratios have the same mathematical value. All undefined ratios are
+    /// equivalent to one another.
+    pub fn equivalent_to(&self, other: &Self) -> bool {
+        self.cmp_value(other) == Ordering::Equal
+    }
+}
+
+impl<T: Count> PartialEq for Equivalent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.equivalent_to(&other.0)
+    }
+}
+
+impl<T: Count> Eq for Equivalent<T> {}
+
+impl<T: Count + Hash> Hash for Equivalent<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.0.defined() {
+            self.0.as_reduced().hash(state);
+        } else {
+            (T::zero(), T::zero()).hash(state);
+        }
+    }
+}
+
+impl<T: Count> PartialOrd for Equivalent<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Undefined ratios precede all defined ratios.
+impl<T: Count> Ord for Equivalent<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_value(&other.0)
+    }
+}
+
+impl<T: Count> From<GenericCountingRatio<T>> for Equivalent<T> {
+    fn from(ratio: GenericCountingRatio<T>) -> Self {
+        Self(ratio)
+    }
+}
@@ -0,0 +1,24 @@
+use core::fmt::{Display, Formatter};
+
+/// Why a ratio could not be built or updated without leaving a nonsensical state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RatioError {
+    /// More matches than observations, which would describe a proportion above 100%.
+    MatchesExceedObservations,
+    /// A count that is negative or too large for the ratio's count type.
+    Overflow,
+}
+
+impl Display for RatioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MatchesExceedObservations => {
+                write!(f, "a ratio cannot have more matches than observations")
+            }
+            Self::Overflow => write!(f, "count does not fit in the ratio's count type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RatioError {}
@@ -0,0 +1,117 @@
+//! An `ErrorBudget` tracks a service level objective, such as 99.9% of requests succeeding,
+//! by counting successes in fixed-width time buckets. Any look-back window can then report
+//! how much of its error budget remains and how fast the budget is burning. Timestamps are
+//! plain integers in whatever unit the caller prefers, such as seconds since the epoch.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, ErrorBudget};
+//!
+//! // A 99% objective, with one-minute buckets of timestamps in seconds.
+//! let mut budget = ErrorBudget::new(0.99, 60);
+//! for second in 0..3600 {
+//!     budget.observe(second, second % 200 != 0);
+//! }
+//!
+//! assert_eq!(budget.successes(3600, 3600), CountingRatio::ratio(3582, 3600));
+//! // 18 errors of the 36 allowed.
+//! assert!((budget.remaining_budget(3600, 3600).unwrap() - 0.5).abs() < 1e-9);
+//! assert!((budget.burn_rate(3600, 3600).unwrap() - 0.5).abs() < 1e-9);
+//! ```
+
+use crate::CountingRatio;
+use alloc::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorBudget {
+    objective: f64,
+    bucket_width: u64,
+    buckets: BTreeMap<u64, CountingRatio>,
+}
+
+impl ErrorBudget {
+    /// `objective` is the target fraction of successes, which must lie strictly between 0
+    /// and 1, and `bucket_width` the span of timestamps counted together, which must be
+    /// positive.
+    pub fn new(objective: f64, bucket_width: u64) -> Self {
+        assert!(
+            objective > 0.0 && objective < 1.0,
+            "objective must lie strictly between 0 and 1"
+        );
+        assert!(bucket_width > 0, "bucket width must be positive");
+        Self {
+            objective,
+            bucket_width,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn objective(&self) -> f64 {
+        self.objective
+    }
+
+    pub fn bucket_width(&self) -> u64 {
+        self.bucket_width
+    }
+
+    pub fn observe(&mut self, timestamp: u64, success: bool) {
+        self.bucket(timestamp).observe(success);
+    }
+
+    /// Adds successes counted elsewhere, all attributed to `timestamp`.
+    pub fn add(&mut self, timestamp: u64, successes: CountingRatio) {
+        *self.bucket(timestamp) += successes;
+    }
+
+    fn bucket(&mut self, timestamp: u64) -> &mut CountingRatio {
+        self.buckets
+            .entry(timestamp / self.bucket_width)
+            .or_default()
+    }
+
+    /// The successes within the buckets that overlap the `lookback` timestamps ending just
+    /// before `now`. The window is empty when `lookback` or `now` is zero.
+    ///
+    /// ```
+    /// use counting_ratio::{CountingRatio, ErrorBudget};
+    ///
+    /// let mut budget = ErrorBudget::new(0.99, 60);
+    /// budget.observe(0, true);
+    /// budget.observe(30, true);
+    /// assert_eq!(budget.successes(60, 60), CountingRatio::ratio(2, 2));
+    /// assert_eq!(budget.successes(60, 0), CountingRatio::new());
+    /// assert_eq!(budget.successes(0, 60), CountingRatio::new());
+    /// assert_eq!(budget.burn_rate(60, 0), None);
+    /// ```
+    pub fn successes(&self, now: u64, lookback: u64) -> CountingRatio {
+        if lookback == 0 || now == 0 {
+            return CountingRatio::new();
+        }
+        let first = now.saturating_sub(lookback) / self.bucket_width;
+        let last = now.saturating_sub(1) / self.bucket_width;
+        self.buckets
+            .range(first..=last)
+            .map(|(_, ratio)| ratio)
+            .sum()
+    }
+
+    /// The fraction of the window's error budget that is left, which is negative once the
+    /// budget is overspent. Returns `None` if the window holds no observations.
+    pub fn remaining_budget(&self, now: u64, lookback: u64) -> Option<f64> {
+        Some(1.0 - self.burn_rate(now, lookback)?)
+    }
+
+    /// The window's error rate as a multiple of the rate the objective allows. A burn rate
+    /// of 1 spends the budget exactly; higher rates exhaust it early. Returns `None` if the
+    /// window holds no observations.
+    pub fn burn_rate(&self, now: u64, lookback: u64) -> Option<f64> {
+        let error_rate = 1.0 - self.successes(now, lookback).value()?;
+        Some(error_rate / (1.0 - self.objective))
+    }
+
+    /// Discards every bucket that ends before `timestamp`.
+    pub fn prune_before(&mut self, timestamp: u64) {
+        let first_kept = timestamp / self.bucket_width;
+        self.buckets = self.buckets.split_off(&first_kept);
+    }
+}
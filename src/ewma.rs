@@ -0,0 +1,113 @@
+//! An `EwmaChart` is an exponentially weighted moving average control chart for a
+//! proportion. Each observation pulls the weighted proportion toward itself by a fraction
+//! `lambda`, and the control limits around the target widen with the number of
+//! observations until they settle at their asymptotic width. Small `lambda` makes the chart
+//! sensitive to small but sustained shifts, complementing `CusumChart`.
+//!
+//! ```
+//! use counting_ratio::{ControlSignal, EwmaChart};
+//!
+//! let mut defects = EwmaChart::new(0.1, 0.1, 3.0);
+//! for i in 0..100 {
+//!     assert_eq!(defects.observe(i % 10 == 9), None);
+//! }
+//!
+//! let signal = (0..20).find_map(|i| defects.observe(i % 2 == 0));
+//! assert_eq!(signal, Some(ControlSignal::AboveUpperLimit));
+//! let (_, upper) = defects.limits();
+//! assert!(defects.statistic() > upper);
+//! ```
+
+use crate::ControlSignal;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EwmaChart {
+    target: f64,
+    lambda: f64,
+    width: f64,
+    statistic: f64,
+    observations: u64,
+}
+
+impl EwmaChart {
+    /// `target` is the in-control proportion, which must lie within `[0.0, 1.0]`.
+    /// `lambda`, the weight given to each new observation, must lie within `(0.0, 1.0]`.
+    /// The control limits lie `width` standard deviations from the target.
+    pub fn new(target: f64, lambda: f64, width: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&target),
+            "EwmaChart target must lie within [0.0, 1.0]"
+        );
+        assert!(
+            lambda > 0.0 && lambda <= 1.0,
+            "EwmaChart lambda must lie within (0.0, 1.0]"
+        );
+        assert!(width > 0.0, "EwmaChart width must be positive");
+        Self {
+            target,
+            lambda,
+            width,
+            statistic: target,
+            observations: 0,
+        }
+    }
+
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    pub fn observations(&self) -> u64 {
+        self.observations
+    }
+
+    /// The exponentially weighted proportion, which starts at the target.
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Updates the weighted proportion, returning a signal if it falls outside the control
+    /// limits.
+    pub fn observe(&mut self, condition_met: bool) -> Option<ControlSignal> {
+        let x = if condition_met { 1.0 } else { 0.0 };
+        self.statistic += self.lambda * (x - self.statistic);
+        self.observations += 1;
+        self.signal()
+    }
+
+    /// The current signal, if the weighted proportion lies outside the control limits.
+    pub fn signal(&self) -> Option<ControlSignal> {
+        let (lower, upper) = self.limits();
+        if self.statistic > upper {
+            Some(ControlSignal::AboveUpperLimit)
+        } else if self.statistic < lower {
+            Some(ControlSignal::BelowLowerLimit)
+        } else {
+            None
+        }
+    }
+
+    /// The lower and upper control limits after the observations made so far, clamped to
+    /// `[0.0, 1.0]`.
+    pub fn limits(&self) -> (f64, f64) {
+        let steps = self.observations.min(i32::MAX as u64 / 2) as i32;
+        let decay = (1.0 - self.lambda).powi(2 * steps);
+        let variance =
+            self.target * (1.0 - self.target) * self.lambda / (2.0 - self.lambda) * (1.0 - decay);
+        let margin = self.width * variance.sqrt();
+        (
+            (self.target - margin).max(0.0),
+            (self.target + margin).min(1.0),
+        )
+    }
+
+    /// Restarts the weighted proportion at the target.
+    pub fn reset(&mut self) {
+        self.statistic = self.target;
+        self.observations = 0;
+    }
+}
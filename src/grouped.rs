@@ -0,0 +1,104 @@
+//! A `GroupedRatio` keeps a separate `CountingRatio` for each key, such as the pass rate of
+//! each test suite, alongside the pooled ratio across all keys.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, GroupedRatio};
+//!
+//! let mut suites = GroupedRatio::new();
+//! for (suite, passed) in [("unit", true), ("unit", true), ("integration", false), ("unit", false), ("integration", true)] {
+//!     suites.observe(suite, passed);
+//! }
+//!
+//! assert_eq!(suites.get(&"unit"), CountingRatio::ratio(2, 3));
+//! assert_eq!(suites.pooled(), CountingRatio::ratio(3, 5));
+//! assert_eq!(
+//!     format!("{suites}"),
+//!     "integration: 1/2 (50.00%)\nunit: 2/3 (66.67%)\n"
+//! );
+//! ```
+
+use crate::CountingRatio;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupedRatio<K: Ord> {
+    groups: BTreeMap<K, CountingRatio>,
+}
+
+impl<K: Ord> GroupedRatio<K> {
+    pub fn new() -> Self {
+        Self {
+            groups: BTreeMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, key: K, condition_met: bool) {
+        self.groups.entry(key).or_default().observe(condition_met);
+    }
+
+    pub fn observe_with_prior(
+        &mut self,
+        key: K,
+        prior_condition_met: bool,
+        posterior_condition_met: bool,
+    ) {
+        if prior_condition_met {
+            self.observe(key, posterior_condition_met);
+        }
+    }
+
+    pub fn add(&mut self, key: K, counts: CountingRatio) {
+        *self.groups.entry(key).or_default() += counts;
+    }
+
+    /// Returns the ratio for `key`, which is undefined if `key` was never observed.
+    pub fn get(&self, key: &K) -> CountingRatio {
+        self.groups.get(key).copied().unwrap_or_default()
+    }
+
+    pub fn pooled(&self) -> CountingRatio {
+        self.groups.values().sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.groups.keys()
+    }
+
+    /// Iterates over every key and its ratio in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, CountingRatio)> {
+        self.groups.iter().map(|(key, ratio)| (key, *ratio))
+    }
+
+    /// Returns every key and its ratio, from highest to lowest ratio.
+    pub fn sorted_by_ratio(&self) -> Vec<(&K, CountingRatio)> {
+        let mut result: Vec<(&K, CountingRatio)> = self.iter().collect();
+        result.sort_by(|(_, a), (_, b)| b.cmp(a));
+        result
+    }
+}
+
+impl<K: Ord> Default for GroupedRatio<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Display> Display for GroupedRatio<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (key, ratio) in self.iter() {
+            writeln!(f, "{key}: {ratio}")?;
+        }
+        Ok(())
+    }
+}
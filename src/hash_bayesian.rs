@@ -0,0 +1,151 @@
+//! A `HashBayesianCounter` offers the counting and probability methods of
+//! `BayesianCounter`, but stores its counts in `HashMap`s. Its labels and examples need only
+//! be `Hash + Eq`, and it keeps running totals for every label and example, so that lookups
+//! stay cheap even with very large vocabularies. The price is that labels and examples are
+//! visited in no particular order.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, HashBayesianCounter};
+//!
+//! let mut bayesian = HashBayesianCounter::new();
+//! for word in "the cat sat on the mat".split(' ') {
+//!     bayesian.observe(word, "cats");
+//! }
+//! for word in "the dog ate the bone".split(' ') {
+//!     bayesian.observe(word, "dogs");
+//! }
+//!
+//! assert_eq!(bayesian.label_count("cats"), 6);
+//! assert_eq!(bayesian.example_count("the"), 4);
+//! assert_eq!(bayesian.p_example_given_label("the", "dogs"), CountingRatio::ratio(2, 5));
+//! assert_eq!(bayesian.p_label_given_example("cats", "mat"), CountingRatio::ratio(66, 66));
+//! ```
+
+use crate::CountingRatio;
+use core::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use trait_set::trait_set;
+
+trait_set! {
+    pub trait HashCountable = Clone + Hash + Eq + Debug;
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HashBayesianCounter<L: HashCountable, S: HashCountable> {
+    counts: HashMap<L, HashMap<S, u64>>,
+    label_counts: HashMap<L, u64>,
+    example_counts: HashMap<S, u64>,
+    total: u64,
+    smoothing: u64,
+}
+
+impl<L: HashCountable, S: HashCountable> HashBayesianCounter<L, S> {
+    pub fn new() -> Self {
+        Self::with_smoothing(0)
+    }
+
+    /// Adds `alpha` pseudo-observations of every known example to every label when
+    /// computing `p_example_given_label()`, as with `BayesianCounter::with_smoothing()`.
+    pub fn with_smoothing(alpha: u64) -> Self {
+        Self {
+            counts: HashMap::new(),
+            label_counts: HashMap::new(),
+            example_counts: HashMap::new(),
+            total: 0,
+            smoothing: alpha,
+        }
+    }
+
+    pub fn smoothing(&self) -> u64 {
+        self.smoothing
+    }
+
+    pub fn observe(&mut self, example: S, label: L) {
+        *self.example_counts.entry(example.clone()).or_default() += 1;
+        *self.label_counts.entry(label.clone()).or_default() += 1;
+        *self
+            .counts
+            .entry(label)
+            .or_default()
+            .entry(example)
+            .or_default() += 1;
+        self.total += 1;
+    }
+
+    /// Adds every count of `other` to this counter.
+    pub fn merge(&mut self, other: &Self) {
+        for (label, counter) in other.counts.iter() {
+            let mine = self.counts.entry(label.clone()).or_default();
+            for (example, count) in counter.iter() {
+                *mine.entry(example.clone()).or_default() += *count;
+            }
+        }
+        for (label, count) in other.label_counts.iter() {
+            *self.label_counts.entry(label.clone()).or_default() += *count;
+        }
+        for (example, count) in other.example_counts.iter() {
+            *self.example_counts.entry(example.clone()).or_default() += *count;
+        }
+        self.total += other.total;
+    }
+
+    pub fn count(&self, example: S, label: L) -> u64 {
+        self.count_of(&example, &label)
+    }
+
+    pub fn label_count(&self, label: L) -> u64 {
+        self.label_counts.get(&label).copied().unwrap_or(0)
+    }
+
+    pub fn example_count(&self, example: S) -> u64 {
+        self.example_counts.get(&example).copied().unwrap_or(0)
+    }
+
+    fn count_of(&self, example: &S, label: &L) -> u64 {
+        self.counts
+            .get(label)
+            .map_or(0, |t| t.get(example).copied().unwrap_or(0))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Every label observed so far, in no particular order.
+    pub fn labels(&self) -> impl Iterator<Item = L> + '_ {
+        self.label_counts.keys().cloned()
+    }
+
+    /// Every distinct example observed so far, under any label.
+    pub fn examples(&self) -> HashSet<S> {
+        self.example_counts.keys().cloned().collect()
+    }
+
+    pub fn p_label(&self, label: L) -> CountingRatio {
+        CountingRatio::ratio(self.label_count(label), self.total)
+    }
+
+    pub fn p_example(&self, example: S) -> CountingRatio {
+        CountingRatio::ratio(self.example_count(example), self.total)
+    }
+
+    pub fn p_example_given_label(&self, example: S, label: L) -> CountingRatio {
+        CountingRatio::ratio(
+            self.count_of(&example, &label) + self.smoothing,
+            self.label_count(label) + self.smoothing * self.example_counts.len() as u64,
+        )
+    }
+
+    pub fn p_label_given_example(&self, label: L, example: S) -> CountingRatio {
+        self.p_example_given_label(example.clone(), label.clone()) * self.p_label(label)
+            / self.p_example(example)
+    }
+}
+
+impl<L: HashCountable, S: HashCountable> Default for HashBayesianCounter<L, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,300 @@
+use crate::special;
+use crate::{
+    BayesianCounter, Count, Countable, CountingRatio, GenericCountingRatio, OutcomeCounter,
+};
+use std::collections::BTreeMap;
+
+/// The outcome of a two-proportion z-test.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ZTestResult {
+    pub z: f64,
+    /// Two-sided p-value for the hypothesis that both proportions are equal.
+    pub p_value: f64,
+}
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// Tests whether the proportions underlying `self` and `other` differ, using the pooled
+    /// two-proportion z-test. Returns `None` if either ratio is undefined, or if the pooled
+    /// proportion is zero or one, leaving no variance to test against.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let control = CountingRatio::ratio(200, 1000);
+    /// let variant = CountingRatio::ratio(250, 1000);
+    /// let result = variant.z_test(&control).unwrap();
+    /// assert!((result.z - 2.6774).abs() < 1e-4);
+    /// assert!((result.p_value - 0.00742).abs() < 1e-5);
+    /// ```
+    pub fn z_test(&self, other: &Self) -> Option<ZTestResult> {
+        let (m1, n1) = self.counts_f64();
+        let (m2, n2) = other.counts_f64();
+        if n1 == 0.0 || n2 == 0.0 {
+            return None;
+        }
+        let pooled = (m1 + m2) / (n1 + n2);
+        let standard_error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+        if standard_error == 0.0 {
+            return None;
+        }
+        let z = (m1 / n1 - m2 / n2) / standard_error;
+        Some(ZTestResult {
+            z,
+            p_value: special::normal_two_sided_p(z),
+        })
+    }
+
+    /// Tests whether the underlying proportion differs from `p0` using the normal
+    /// approximation to the binomial distribution, which suits large numbers of
+    /// observations. Returns `None` if the ratio is undefined or `p0` is zero or one.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let heads = CountingRatio::ratio(560, 1000);
+    /// let result = heads.binomial_z_test(0.5).unwrap();
+    /// assert!((result.z - 3.7947).abs() < 1e-4);
+    /// assert!(result.p_value < 0.001);
+    /// ```
+    pub fn binomial_z_test(&self, p0: f64) -> Option<ZTestResult> {
+        assert!((0.0..=1.0).contains(&p0), "p0 must lie within [0.0, 1.0]");
+        let (m, n) = self.counts_f64();
+        if n == 0.0 || p0 == 0.0 || p0 == 1.0 {
+            return None;
+        }
+        let z = (m / n - p0) / (p0 * (1.0 - p0) / n).sqrt();
+        Some(ZTestResult {
+            z,
+            p_value: special::normal_two_sided_p(z),
+        })
+    }
+}
+
+impl CountingRatio {
+    /// The exact two-sided p-value for the hypothesis that the underlying proportion is
+    /// `p0`: the probability, under that hypothesis, of counts no more likely than those
+    /// observed. An undefined ratio yields 1. Panics if the ratio holds more matches than
+    /// observations.
+    ///
+    /// Counts whose probabilities are too small to represent as an `f64` are skipped, so
+    /// the cost grows with the square root of the number of observations.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let heads = CountingRatio::ratio(9, 12);
+    /// assert!((heads.binomial_test(0.5) - 0.14600).abs() < 1e-5);
+    /// assert_eq!(CountingRatio::ratio(3, 3).binomial_test(1.0), 1.0);
+    ///
+    /// let fair = CountingRatio::ratio(2_000_000_000, 4_000_000_000);
+    /// assert!(fair.binomial_test(0.5) > 0.99);
+    /// ```
+    pub fn binomial_test(&self, p0: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p0), "p0 must lie within [0.0, 1.0]");
+        let (m, n) = (self.matches(), self.observations());
+        assert!(m <= n, "matches must not exceed observations");
+        if p0 == 0.0 || p0 == 1.0 {
+            let expected = if p0 == 0.0 { 0 } else { n };
+            return if m == expected { 1.0 } else { 0.0 };
+        }
+        let ln_probability =
+            |x: u64| ln_choose(n, x) + x as f64 * p0.ln() + (n - x) as f64 * (1.0 - p0).ln();
+        // Relative tolerance guards against rounding when comparing equally likely counts.
+        let threshold = ln_probability(m) + 1e-7;
+        let mode = (((n as f64 + 1.0) * p0) as u64).min(n);
+        sum_at_most(threshold, 0, mode, n, ln_probability)
+    }
+}
+
+/// Fisher's exact test of whether the proportions underlying `a` and `b` differ, returning
+/// the two-sided p-value. Unlike `z_test()`, it remains valid for small counts. Panics if
+/// either ratio holds more matches than observations, or if the combined observations
+/// overflow a `u64`.
+///
+/// As with `binomial_test()`, tables too improbable to represent as an `f64` are skipped,
+/// so the cost grows with the square root of the counts.
+///
+/// ```
+/// use counting_ratio::{fisher_exact, CountingRatio};
+///
+/// let p = fisher_exact(CountingRatio::ratio(1, 10), CountingRatio::ratio(7, 9));
+/// assert!((p - 0.005477).abs() < 1e-6);
+///
+/// let same = CountingRatio::ratio(1_000_000_000, 2_000_000_000);
+/// assert!(fisher_exact(same, same) > 0.99);
+/// ```
+pub fn fisher_exact(a: CountingRatio, b: CountingRatio) -> f64 {
+    assert!(
+        a.matches() <= a.observations() && b.matches() <= b.observations(),
+        "matches must not exceed observations"
+    );
+    let total = a
+        .observations()
+        .checked_add(b.observations())
+        .expect("combined observations must fit in a u64");
+    // Cannot overflow, as the matches of each ratio are at most its observations.
+    let matches = a.matches() + b.matches();
+    let ln_probability = |x: u64| {
+        ln_choose(a.observations(), x) + ln_choose(b.observations(), matches - x)
+            - ln_choose(total, matches)
+    };
+    let observed = ln_probability(a.matches());
+    let low = matches.saturating_sub(b.observations());
+    let high = a.observations().min(matches);
+    // The hypergeometric mode, floor((matches + 1)(n + 1) / (total + 2)) for the n draws
+    // of `a`, computed in u128 so that the product cannot overflow.
+    let mode =
+        (u128::from(matches) + 1) * (u128::from(a.observations()) + 1) / (u128::from(total) + 2);
+    let mode = (mode as u64).clamp(low, high);
+    // Relative tolerance guards against rounding when comparing equally likely tables.
+    let threshold = observed + 1e-7;
+    sum_at_most(threshold, low, mode, high, ln_probability)
+}
+
+// Below this, the exponential of a log-probability rounds to zero.
+const LN_UNDERFLOW: f64 = -746.0;
+
+// Sums the probabilities of the counts within `low..=high` whose logarithms, given by
+// `ln_probability`, are at most `threshold`. The probabilities fall steadily away from the
+// mode, so walking outward from it until they underflow visits every count that
+// contributes to the sum, at a cost that grows with the spread rather than the range.
+fn sum_at_most(
+    threshold: f64,
+    low: u64,
+    mode: u64,
+    high: u64,
+    ln_probability: impl Fn(u64) -> f64,
+) -> f64 {
+    let below = (low..mode)
+        .rev()
+        .map(&ln_probability)
+        .take_while(|ln_p| *ln_p > LN_UNDERFLOW);
+    let above = (mode..=high)
+        .map(&ln_probability)
+        .take_while(|ln_p| *ln_p > LN_UNDERFLOW);
+    below
+        .chain(above)
+        .filter(|ln_p| *ln_p <= threshold)
+        .map(f64::exp)
+        .sum::<f64>()
+        .min(1.0)
+}
+
+fn ln_choose(n: u64, k: u64) -> f64 {
+    special::ln_gamma(n as f64 + 1.0)
+        - special::ln_gamma(k as f64 + 1.0)
+        - special::ln_gamma((n - k) as f64 + 1.0)
+}
+
+/// The outcome of a chi-square test.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChiSquareResult {
+    pub statistic: f64,
+    pub degrees_of_freedom: u64,
+    pub p_value: f64,
+}
+
+impl ChiSquareResult {
+    fn new(statistic: f64, degrees_of_freedom: u64) -> Self {
+        Self {
+            statistic,
+            degrees_of_freedom,
+            p_value: special::gamma_q(degrees_of_freedom as f64 / 2.0, statistic / 2.0),
+        }
+    }
+}
+
+impl<L: Countable, S: Countable> BayesianCounter<L, S> {
+    /// Tests whether examples and labels are independent, treating the counts of every
+    /// label and example as a contingency table. Returns `None` unless at least two
+    /// labels and two examples have been observed.
+    ///
+    /// ```
+    /// use counting_ratio::BayesianCounter;
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for (example, label, count) in [(1, "A", 30), (2, "A", 10), (1, "B", 15), (2, "B", 25)] {
+    ///     for _ in 0..count {
+    ///         bayesian.observe(example, label);
+    ///     }
+    /// }
+    ///
+    /// let result = bayesian.chi_square_independence().unwrap();
+    /// assert_eq!(result.degrees_of_freedom, 1);
+    /// assert!((result.statistic - 11.4286).abs() < 1e-4);
+    /// assert!(result.p_value < 0.001);
+    /// ```
+    pub fn chi_square_independence(&self) -> Option<ChiSquareResult> {
+        let labels: Vec<L> = self.labels().collect();
+        let examples = self.examples();
+        if labels.len() < 2 || examples.len() < 2 {
+            return None;
+        }
+        let total = self.total() as f64;
+        let mut statistic = 0.0;
+        for label in labels.iter() {
+            let label_count = self.label_count_of(label) as f64;
+            for example in examples.iter() {
+                let expected = label_count * self.example_count_of(example) as f64 / total;
+                let difference = self.count_of(example, label) as f64 - expected;
+                statistic += difference * difference / expected;
+            }
+        }
+        let degrees_of_freedom = (labels.len() as u64 - 1) * (examples.len() as u64 - 1);
+        Some(ChiSquareResult::new(statistic, degrees_of_freedom))
+    }
+}
+
+impl<E: Countable> OutcomeCounter<E> {
+    /// Tests whether the outcomes follow the `expected` proportions, given as relative
+    /// weights that need not sum to one. Every weight must be positive. Returns `None`
+    /// unless there are observations and at least two expected outcomes, or if an outcome
+    /// was observed that has no expected weight.
+    ///
+    /// ```
+    /// use counting_ratio::OutcomeCounter;
+    ///
+    /// let fair = (1..=6).map(|face| (face, 1.0));
+    /// let mut rolls = OutcomeCounter::new();
+    /// for (face, count) in [(1, 8), (2, 12), (3, 9), (4, 11), (5, 10), (6, 10)] {
+    ///     rolls.add(face, count);
+    /// }
+    /// let result = rolls.chi_square_goodness_of_fit(fair.clone()).unwrap();
+    /// assert_eq!(result.degrees_of_freedom, 5);
+    /// assert!((result.statistic - 1.0).abs() < 1e-9);
+    /// assert!(result.p_value > 0.9);
+    ///
+    /// let mut loaded = OutcomeCounter::new();
+    /// for (face, count) in [(1, 20), (2, 5), (3, 5), (4, 5), (5, 5), (6, 20)] {
+    ///     loaded.add(face, count);
+    /// }
+    /// assert!(loaded.chi_square_goodness_of_fit(fair).unwrap().p_value < 0.001);
+    /// ```
+    pub fn chi_square_goodness_of_fit<I: IntoIterator<Item = (E, f64)>>(
+        &self,
+        expected: I,
+    ) -> Option<ChiSquareResult> {
+        let mut weights: BTreeMap<E, f64> = BTreeMap::new();
+        for (outcome, weight) in expected {
+            assert!(weight > 0.0, "expected weights must be positive");
+            *weights.entry(outcome).or_default() += weight;
+        }
+        if self.total() == 0
+            || weights.len() < 2
+            || self
+                .iter()
+                .any(|(outcome, _)| !weights.contains_key(outcome))
+        {
+            return None;
+        }
+        let total_weight: f64 = weights.values().sum();
+        let total = self.total() as f64;
+        let mut statistic = 0.0;
+        for (outcome, weight) in weights.iter() {
+            let expected = total * weight / total_weight;
+            let difference = self.count(outcome) as f64 - expected;
+            statistic += difference * difference / expected;
+        }
+        Some(ChiSquareResult::new(statistic, weights.len() as u64 - 1))
+    }
+}
@@ -0,0 +1,212 @@
+//! Information-theoretic measures over the counts of a `BayesianCounter`. All entropies
+//! are measured in bits.
+//!
+//! ```
+//! use counting_ratio::BayesianCounter;
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! for (outlook, play) in [("sunny", false), ("sunny", false), ("rainy", true), ("rainy", true)] {
+//!     bayesian.observe(outlook, play);
+//! }
+//!
+//! assert_eq!(bayesian.label_entropy(), 1.0);
+//! assert_eq!(bayesian.conditional_entropy(), 0.0);
+//! assert_eq!(bayesian.information_gain(), 1.0);
+//! ```
+
+use crate::{BayesianCounter, Countable};
+use std::collections::BTreeSet;
+
+impl<L: Countable, S: Countable> BayesianCounter<L, S> {
+    /// The entropy of the label distribution, H(L).
+    pub fn label_entropy(&self) -> f64 {
+        entropy(
+            self.labels().map(|label| self.label_count_of(&label)),
+            self.total(),
+        )
+    }
+
+    /// The entropy of the label distribution remaining once the example is known, H(L | S).
+    pub fn conditional_entropy(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        self.examples()
+            .into_iter()
+            .map(|example| {
+                let example_count = self.example_count_of(&example);
+                let weight = example_count as f64 / self.total() as f64;
+                weight
+                    * entropy(
+                        self.labels().map(|label| self.count_of(&example, &label)),
+                        example_count,
+                    )
+            })
+            .sum()
+    }
+
+    /// The reduction in label entropy from knowing the example, H(L) - H(L | S).
+    pub fn information_gain(&self) -> f64 {
+        self.label_entropy() - self.conditional_entropy()
+    }
+
+    /// The mutual information between examples and labels, I(L; S), computed directly
+    /// from the joint counts. It is mathematically equal to `information_gain()`.
+    ///
+    /// ```
+    /// use counting_ratio::BayesianCounter;
+    ///
+    /// let mut bayesian = BayesianCounter::new();
+    /// for (example, label) in [(1, 'a'), (1, 'a'), (1, 'b'), (2, 'b'), (2, 'b'), (3, 'a')] {
+    ///     bayesian.observe(example, label);
+    /// }
+    ///
+    /// assert!((bayesian.mutual_information() - bayesian.information_gain()).abs() < 1e-12);
+    /// assert!((bayesian.mutual_information() - 0.5409).abs() < 1e-4);
+    /// ```
+    pub fn mutual_information(&self) -> f64 {
+        let total = self.total() as f64;
+        let examples = self.examples();
+        let mut result = 0.0;
+        for label in self.labels() {
+            let p_label = self.label_count_of(&label) as f64 / total;
+            for example in examples.iter() {
+                let joint = self.count_of(example, &label) as f64 / total;
+                if joint > 0.0 {
+                    let p_example = self.example_count_of(example) as f64 / total;
+                    result += joint * (joint / (p_label * p_example)).log2();
+                }
+            }
+        }
+        result
+    }
+
+    /// The Kullback–Leibler divergence D(self || other) between the example distributions
+    /// of two counters. Both distributions receive `smoothing` pseudo-observations of every
+    /// example seen by either counter; without smoothing, an example seen only by `self`
+    /// makes the divergence infinite.
+    ///
+    /// ```
+    /// use counting_ratio::BayesianCounter;
+    ///
+    /// let mut last_week = BayesianCounter::new();
+    /// let mut this_week = BayesianCounter::new();
+    /// for (example, count) in [("error", 10), ("ok", 90)] {
+    ///     for _ in 0..count {
+    ///         last_week.observe(example, ());
+    ///     }
+    /// }
+    /// for (example, count) in [("error", 30), ("ok", 60), ("timeout", 10)] {
+    ///     for _ in 0..count {
+    ///         this_week.observe(example, ());
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(this_week.kl_divergence(&last_week, 0.0), f64::INFINITY);
+    /// assert!(this_week.kl_divergence(&last_week, 0.5).is_finite());
+    /// let js = this_week.js_divergence(&last_week);
+    /// assert!(js > 0.0 && js < 1.0);
+    /// assert_eq!(js, last_week.js_divergence(&this_week));
+    /// ```
+    pub fn kl_divergence(&self, other: &Self, smoothing: f64) -> f64 {
+        let support = self.examples().union(&other.examples()).cloned().collect();
+        kl_divergence(&distribution_pair(
+            &support,
+            |example| self.example_count_of(example),
+            |example| other.example_count_of(example),
+            smoothing,
+        ))
+    }
+
+    /// The Jensen–Shannon divergence between the example distributions of two counters.
+    /// It is symmetric, always finite, and lies between 0 and 1 bit, so zero counts need no
+    /// smoothing.
+    pub fn js_divergence(&self, other: &Self) -> f64 {
+        let support = self.examples().union(&other.examples()).cloned().collect();
+        js_divergence(&distribution_pair(
+            &support,
+            |example| self.example_count_of(example),
+            |example| other.example_count_of(example),
+            0.0,
+        ))
+    }
+
+    /// The Kullback–Leibler divergence D(p(S | `label`) || p(S | `other_label`)) between the
+    /// example distributions of two labels, smoothed as in `kl_divergence()`.
+    pub fn label_kl_divergence(&self, label: L, other_label: L, smoothing: f64) -> f64 {
+        kl_divergence(&distribution_pair(
+            &self.examples(),
+            |example| self.count_of(example, &label),
+            |example| self.count_of(example, &other_label),
+            smoothing,
+        ))
+    }
+
+    /// The Jensen–Shannon divergence between the example distributions of two labels.
+    pub fn label_js_divergence(&self, label: L, other_label: L) -> f64 {
+        js_divergence(&distribution_pair(
+            &self.examples(),
+            |example| self.count_of(example, &label),
+            |example| self.count_of(example, &other_label),
+            0.0,
+        ))
+    }
+}
+
+// Pairs the probabilities that two distributions assign to every example in `support`,
+// after adding `smoothing` to every count.
+fn distribution_pair<S: Countable, P: Fn(&S) -> u64, Q: Fn(&S) -> u64>(
+    support: &BTreeSet<S>,
+    p_count: P,
+    q_count: Q,
+    smoothing: f64,
+) -> Vec<(f64, f64)> {
+    let smoothed: Vec<(f64, f64)> = support
+        .iter()
+        .map(|example| {
+            (
+                p_count(example) as f64 + smoothing,
+                q_count(example) as f64 + smoothing,
+            )
+        })
+        .collect();
+    let p_total: f64 = smoothed.iter().map(|(p, _)| p).sum();
+    let q_total: f64 = smoothed.iter().map(|(_, q)| q).sum();
+    smoothed
+        .iter()
+        .map(|(p, q)| (p / p_total, q / q_total))
+        .collect()
+}
+
+fn kl_divergence(pairs: &[(f64, f64)]) -> f64 {
+    pairs
+        .iter()
+        .filter(|(p, _)| *p > 0.0)
+        .map(|(p, q)| p * (p / q).log2())
+        .sum()
+}
+
+fn js_divergence(pairs: &[(f64, f64)]) -> f64 {
+    let to_mixture = |pick: fn(&(f64, f64)) -> f64| -> Vec<(f64, f64)> {
+        pairs
+            .iter()
+            .map(|pair| (pick(pair), (pair.0 + pair.1) / 2.0))
+            .collect()
+    };
+    (kl_divergence(&to_mixture(|pair| pair.0)) + kl_divergence(&to_mixture(|pair| pair.1))) / 2.0
+}
+
+// Entropy in bits of the distribution given by `counts`, which sum to `total`.
+fn entropy<I: Iterator<Item = u64>>(counts: I, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    counts
+        .filter(|count| *count > 0)
+        .map(|count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
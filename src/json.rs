@@ -0,0 +1,48 @@
+//! Human-readable JSON for `CountingRatio` and `BayesianCounter`, convenient for inspecting
+//! and diffing trained models. A counter's counts are nested by label, then by example.
+//! Requires the `json` feature.
+//!
+//! ```
+//! use counting_ratio::{BayesianCounter, CountingRatio};
+//!
+//! let ratio = CountingRatio::ratio(3, 10);
+//! assert_eq!(ratio.to_json(), "{\n  \"matches\": 3,\n  \"observations\": 10\n}");
+//! assert_eq!(CountingRatio::from_json(&ratio.to_json()).unwrap(), ratio);
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("buy", "spam");
+//! bayesian.observe("hello", "ham");
+//!
+//! let json = bayesian.to_json().unwrap();
+//! assert!(json.contains("\"spam\": {\n      \"buy\": 2\n    }"));
+//! let restored: BayesianCounter<String, String> = BayesianCounter::from_json(&json).unwrap();
+//! assert_eq!(restored.count("buy".to_string(), "spam".to_string()), 2);
+//! ```
+
+use crate::{BayesianCounter, Count, Countable, GenericCountingRatio};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+impl<T: Count + Serialize + DeserializeOwned> GenericCountingRatio<T> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("counts always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl<L: Countable + Serialize, S: Countable + Serialize> BayesianCounter<L, S> {
+    /// Fails if a label or example cannot be written as a JSON object key, as with tuples.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl<L: Countable + DeserializeOwned, S: Countable + DeserializeOwned> BayesianCounter<L, S> {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
@@ -17,10 +17,35 @@
 //!     observations.observe(i % 7 == 0);
 //! }
 //!
-//! assert_eq!(0.15, observations.into());
+//! assert_eq!(0.15, f64::from(observations));
 //! assert_eq!("15/100 (15.00%)", format!("{observations}").as_str());
 //! ```
 //!
+//! A `CountingRatio` can also be collected directly from an iterator of observations.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let mut observations: CountingRatio = (0..100).map(|i| i % 7 == 0).collect();
+//! assert_eq!("15/100 (15.00%)", format!("{observations}").as_str());
+//!
+//! observations.extend((0..20).map(|i| i % 4 == 0));
+//! assert_eq!("20/120 (16.67%)", format!("{observations}").as_str());
+//! ```
+//!
+//! The `CountRatioIterExt` trait offers the same measurement as a single chained call,
+//! optionally with a prior condition as described below.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, CountRatioIterExt};
+//!
+//! assert_eq!((0..100).count_ratio(|i| i % 7 == 0), CountingRatio::ratio(15, 100));
+//! assert_eq!(
+//!     ["bear", "", "zoo", "ape"].iter().count_ratio_with_prior(|s| s.len() > 0, |s| s.contains("a")),
+//!     CountingRatio::ratio(2, 3)
+//! );
+//! ```
+//!
 //! In more complex situations, it may be helpful to supply a prior condition as well as a
 //! posterior condition. In this example, we want to count the number of strings that contain
 //! a vowel, but we want to ignore empty strings entirely.
@@ -37,10 +62,57 @@
 //!     observations.observe_with_prior(s.len() > 0, s.contains("a"));
 //! }
 //!
-//! assert_eq!(0.375, observations.into());
+//! assert_eq!(0.375, f64::from(observations));
 //! assert_eq!("3/8 (37.50%)", format!("{observations}").as_str());
 //! ```
 //!
+//! The raw counts remain available alongside the derived rate.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let observations = CountingRatio::ratio(15, 100);
+//! assert_eq!(observations.matches(), 15);
+//! assert_eq!(observations.misses(), 85);
+//! assert_eq!(observations.observations(), 100);
+//! assert_eq!(observations.rate(), 0.15);
+//! ```
+//!
+//! Converting an undefined ratio into `f64` yields `NaN`. The `value()` and `value_or()`
+//! methods make the undefined case explicit instead.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let fresh = CountingRatio::new();
+//! assert!(f64::from(fresh).is_nan());
+//! assert_eq!(fresh.value(), None);
+//! assert_eq!(fresh.value_or(0.0), 0.0);
+//! assert_eq!(CountingRatio::ratio(1, 4).value(), Some(0.25));
+//! ```
+//!
+//! A `CountingRatio` can be parsed back from either its bare counts or its `Display` form.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, ParseRatioError};
+//!
+//! assert_eq!("15/100".parse(), Ok(CountingRatio::ratio(15, 100)));
+//! assert_eq!("15/100 (15.00%)".parse(), Ok(CountingRatio::ratio(15, 100)));
+//! assert_eq!("15:100".parse::<CountingRatio>(), Err(ParseRatioError::MissingSeparator));
+//! ```
+//!
+//! The formatter's precision controls the decimal places of the percentage, and the
+//! alternate form shows the percentage alone.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let rare = CountingRatio::ratio(3, 25000);
+//! assert_eq!("3/25000 (0.01%)", format!("{rare}").as_str());
+//! assert_eq!("3/25000 (0.0120%)", format!("{rare:.4}").as_str());
+//! assert_eq!("0.012%", format!("{rare:#.3}").as_str());
+//! ```
+//!
 //! `CountingRatio` objects can also be added together. Because they represent counted observations,
 //! the numerators and denominators are added together to produce the sum.
 //!
@@ -58,10 +130,25 @@
 //! }
 //!
 //! let obs3 = obs1 + obs2;
-//! assert_eq!(0.16666666666666666, obs3.into());
+//! assert_eq!(0.16666666666666666, f64::from(obs3));
 //! assert_eq!("20/120 (16.67%)", format!("{obs3}").as_str());
 //! ```
 //!
+//! Observations can also be retracted, for example when late corrections arrive. Retracting
+//! observations that were never made panics; `checked_sub()` returns `None` instead.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let mut observations = CountingRatio::ratio(20, 120);
+//! observations.unobserve(true);
+//! assert_eq!(observations, CountingRatio::ratio(19, 119));
+//!
+//! let corrected = observations - CountingRatio::ratio(4, 19);
+//! assert_eq!(corrected, CountingRatio::ratio(15, 100));
+//! assert_eq!(corrected.checked_sub(CountingRatio::ratio(0, 90)), None);
+//! ```
+//!
 //! `CountingRatio` objects might also be created from other sources. This can
 //! be useful for comparing ratios without resorting to floating-point calculations,
 //! as `CountingRatio` objects implement `Ord` purely with integer arithmetic.
@@ -76,11 +163,17 @@
 //!
 //! let ratio2 = CountingRatio::ratio(3, 7);
 //! assert!(ratio1 < ratio2);
+//!
+//! let huge = CountingRatio::ratio(u64::MAX - 1, u64::MAX);
+//! let larger = CountingRatio::ratio(u64::MAX - 1, u64::MAX - 1);
+//! assert!(huge < larger);
 //! ```
 //!
 //! `CountingRatio` objects lend themselves to Bayesian calculations.
 //!
 //! ```
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
 //! use counting_ratio::{CountingRatio, BayesianCounter};
 //!
 //! let nums1 = vec![1, 3, 3, 5, 6, 7, 9, 11, 12, 13];
@@ -102,77 +195,919 @@
 //! assert_eq!(bayesian.p_example_given_label(3, "One"), CountingRatio::ratio(2, 10));
 //! assert_eq!(bayesian.p_example_given_label(3, "Two"), CountingRatio::ratio(1, 6));
 //! assert_eq!(bayesian.p_label_given_example("One", 3), CountingRatio::ratio(2 * 10 * 16, 10 * 16 * 3));
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+//!
+//! Counters built from separate shards of data can be merged.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
+//! use counting_ratio::{CountingRatio, BayesianCounter};
+//!
+//! let mut shard1 = BayesianCounter::new();
+//! shard1.observe(1, "One");
+//! shard1.observe(3, "One");
+//! let mut shard2 = BayesianCounter::new();
+//! shard2.observe(3, "One");
+//! shard2.observe(3, "Two");
+//!
+//! let merged = shard1 + shard2;
+//! assert_eq!(merged.count(3, "One"), 2);
+//! assert_eq!(merged.p_label("One"), CountingRatio::ratio(3, 4));
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+//!
+//! With sparse data, smoothing keeps unseen examples from receiving a probability of zero.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
+//! use counting_ratio::{CountingRatio, BayesianCounter};
+//!
+//! let mut bayesian = BayesianCounter::with_smoothing(1);
+//! for (num, label) in [(1, "One"), (3, "One"), (3, "Two"), (4, "Two"), (4, "Two")] {
+//!     bayesian.observe(num, label);
+//! }
+//!
+//! assert_eq!(bayesian.p_example_given_label(4, "One"), CountingRatio::ratio(1, 5));
+//! assert_eq!(bayesian.p_example_given_label(4, "Two"), CountingRatio::ratio(3, 6));
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+//!
+//! Labels and examples need only be `Clone + Ord + Debug`, so owned values such as
+//! `String` work as keys.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # fn main() {
+//! use counting_ratio::{CountingRatio, BayesianCounter};
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! for line in ["spam buy now", "ham see you soon", "spam buy cheap"] {
+//!     let (label, words) = line.split_once(' ').unwrap();
+//!     for word in words.split(' ') {
+//!         bayesian.observe(word.to_string(), label.to_string());
+//!     }
+//! }
+//!
+//! assert_eq!(bayesian.count("buy".to_string(), "spam".to_string()), 2);
+//! assert_eq!(bayesian.p_label("ham".to_string()), CountingRatio::ratio(3, 7));
+//! assert_eq!(bayesian.label_ranking_for("buy".to_string())[0], "spam");
+//! # }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
 //! ```
+//!
+//! `CountingRatio` stores its counts as `u64`. Other unsigned integer types are available
+//! through `GenericCountingRatio`.
+//!
+//! ```
+//! use counting_ratio::GenericCountingRatio;
+//!
+//! let mut compact = GenericCountingRatio::<u32>::new();
+//! for i in 0..100 {
+//!     compact.observe(i % 7 == 0);
+//! }
+//!
+//! assert_eq!("15/100 (15.00%)", format!("{compact}").as_str());
+//! assert_eq!(compact.cast::<u128>(), Some(GenericCountingRatio::ratio(15, 100)));
+//! ```
+//!
+//! Repeatedly multiplying ratios quickly overflows `u64`. Converting to a `BigCountingRatio`,
+//! which counts with `u128`, provides considerably more headroom.
+//!
+//! ```
+//! use counting_ratio::{BigCountingRatio, CountingRatio};
+//!
+//! let evidence = CountingRatio::ratio(999_999, 1_000_000);
+//! let mut chain = BigCountingRatio::from(evidence);
+//! for _ in 0..4 {
+//!     chain *= BigCountingRatio::from(evidence);
+//! }
+//!
+//! assert_eq!(chain, BigCountingRatio::ratio(999_999u128.pow(5), 1_000_000u128.pow(5)));
+//! ```
+//!
+//! Enabling the `serde` feature derives `Serialize` and `Deserialize` for both
+//! `CountingRatio` and `BayesianCounter`, so that observations can be persisted between runs.
+//!
+//! The crate supports `no_std` targets when its default `std` feature is disabled. The core
+//! `CountingRatio` types need neither `std` nor an allocator. The `alloc` feature adds the
+//! collection-based types such as `BayesianCounter`, `GroupedRatio`, and `WindowedRatio`,
+//! while `std` adds the types and methods that rely on floating-point functions, such as
+//! `wilson_interval()`. The `rand` feature adds randomized strategies such as
+//! `bandit::ThompsonSampler` and the `simulate` module of synthetic observations, and the
+//! `sketch` feature adds the approximate `SketchCounter`. The `json` feature adds `to_json()` and `from_json()` to
+//! `CountingRatio` and `BayesianCounter`, and the `persist` feature adds
+//! `BayesianCounter::save()` and `BayesianCounter::load()`. The `csv` feature reads and
+//! writes the counts of `BayesianCounter` and `GroupedRatio` as CSV, and the `arrow` and
+//! `parquet` features convert them to Arrow record batches and Parquet files. The
+//! `prometheus` feature exports ratios as Prometheus counters, and the `metrics` feature
+//! emits them through the `metrics` facade. The `opentelemetry` feature adds the `otel`
+//! module, which reports ratios as OpenTelemetry instruments, and the `tracing` feature
+//! adds a `Reporter` that logs them periodically. The `tokio` feature adds
+//! `spawn_reporter()`, which reports shared counters from an async task, and the `plot`
+//! feature adds the `plot` module, which charts ratios with `plotters`. The `rayon` feature
+//! lets `CountingRatio` and `BayesianCounter` be collected from parallel iterators, and the
+//! `bitvec` feature adds `observe_bits()`, which counts a `BitSlice` of observations. The
+//! `num-rational` feature converts ratios to and from `num_rational::Ratio`, and the `approx`
+//! feature compares ratios within a tolerance through the `approx` crate.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Formatter};
-use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign};
-use histogram_macros::*;
-use std::cmp::Ordering;
-use std::collections::BTreeMap;
-use trait_set::trait_set;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use num_traits::{NumAssign, PrimInt, Unsigned};
+
+#[cfg(feature = "std")]
+mod aggregator;
+#[cfg(feature = "approx")]
+mod approx_eq;
+#[cfg(all(feature = "tokio", target_has_atomic = "64"))]
+mod async_reporter;
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+#[cfg(feature = "std")]
+pub mod bandit;
+mod bar;
+#[cfg(feature = "alloc")]
+mod bayesian;
+mod beta_ratio;
+#[cfg(feature = "alloc")]
+mod change_point;
+#[cfg(feature = "arrow")]
+pub mod columnar;
+#[cfg(feature = "std")]
+mod concurrent;
+mod continued_fraction;
+#[cfg(feature = "csv")]
+mod csv_io;
+mod cusum;
+mod decaying;
+#[cfg(feature = "alloc")]
+mod dirichlet;
+#[cfg(feature = "std")]
+mod e_value;
+mod equivalent;
+mod error;
+#[cfg(feature = "alloc")]
+mod error_budget;
+#[cfg(feature = "std")]
+mod ewma;
+#[cfg(feature = "alloc")]
+mod grouped;
+#[cfg(feature = "std")]
+mod hash_bayesian;
+#[cfg(feature = "std")]
+mod hypothesis;
+#[cfg(feature = "std")]
+mod information;
+#[cfg(feature = "json")]
+mod json;
+mod locale;
+#[cfg(feature = "std")]
+mod log_probability;
+#[cfg(feature = "alloc")]
+mod markov;
+#[cfg(feature = "alloc")]
+mod markup;
+#[cfg(feature = "alloc")]
+mod matrix;
+#[cfg(feature = "metrics")]
+mod metrics_facade;
+#[cfg(feature = "std")]
+mod naive_bayes;
+#[cfg(feature = "alloc")]
+mod nested;
+#[cfg(feature = "std")]
+mod ngram;
+#[cfg(all(feature = "opentelemetry", target_has_atomic = "64"))]
+pub mod otel;
+#[cfg(feature = "alloc")]
+mod outcome;
+#[cfg(feature = "std")]
+mod p_chart;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parse;
+#[cfg(feature = "persist")]
+mod persist;
+#[cfg(feature = "plot")]
+pub mod plot;
+mod posterior;
+mod probability;
+#[cfg(feature = "prometheus")]
+mod prometheus_metrics;
+mod pseudo_count;
+#[cfg(feature = "alloc")]
+mod ratio_table;
+#[cfg(feature = "num-rational")]
+mod rational;
+#[cfg(feature = "tracing")]
+mod reporter;
+#[cfg(feature = "alloc")]
+mod series;
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+mod sharded;
+#[cfg(feature = "rand")]
+pub mod simulate;
+#[cfg(feature = "sketch")]
+mod sketch;
+#[cfg(feature = "std")]
+mod special;
+#[cfg(feature = "std")]
+pub mod stats;
+mod strict;
+#[cfg(feature = "alloc")]
+mod success;
+#[cfg(feature = "std")]
+mod summary;
+#[cfg(feature = "std")]
+mod table;
+#[cfg(feature = "alloc")]
+mod windowed;
+
+#[cfg(feature = "std")]
+pub use aggregator::{Aggregator, AggregatorSender};
+#[cfg(all(feature = "tokio", target_has_atomic = "64"))]
+pub use async_reporter::{spawn_reporter, SharedRatio};
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicCountingRatio;
+pub use bar::RatioBar;
+#[cfg(feature = "alloc")]
+pub use bayesian::{BayesianCounter, Countable};
+pub use beta_ratio::BetaRatio;
+#[cfg(feature = "alloc")]
+pub use change_point::ChangePointDetector;
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentBayesianCounter;
+pub use cusum::{ControlSignal, CusumChart};
+pub use decaying::DecayingRatio;
+#[cfg(feature = "alloc")]
+pub use dirichlet::DirichletPosterior;
+#[cfg(feature = "std")]
+pub use e_value::EValueTest;
+pub use equivalent::Equivalent;
+pub use error::RatioError;
+#[cfg(feature = "alloc")]
+pub use error_budget::ErrorBudget;
+#[cfg(feature = "std")]
+pub use ewma::EwmaChart;
+#[cfg(feature = "alloc")]
+pub use grouped::GroupedRatio;
+#[cfg(feature = "std")]
+pub use hash_bayesian::{HashBayesianCounter, HashCountable};
+#[cfg(feature = "std")]
+pub use hypothesis::{fisher_exact, ChiSquareResult, ZTestResult};
+pub use locale::{LocaleFormat, LocalizedRatio};
+#[cfg(feature = "std")]
+pub use log_probability::LogProbability;
+#[cfg(feature = "alloc")]
+pub use markov::TransitionCounter;
+#[cfg(feature = "alloc")]
+pub use matrix::RatioMatrix;
+#[cfg(feature = "std")]
+pub use naive_bayes::NaiveBayesClassifier;
+#[cfg(feature = "alloc")]
+pub use nested::NestedRatio;
+#[cfg(feature = "std")]
+pub use ngram::NGramCounter;
+#[cfg(feature = "alloc")]
+pub use outcome::OutcomeCounter;
+#[cfg(feature = "std")]
+pub use p_chart::PChart;
+pub use parse::ParseRatioError;
+#[cfg(feature = "persist")]
+pub use persist::PersistError;
+pub use posterior::PosteriorRatio;
+pub use probability::Probability;
+#[cfg(feature = "prometheus")]
+pub use prometheus_metrics::{PrometheusGroupedRatio, PrometheusRatio};
+pub use pseudo_count::PseudoCountRatio;
+#[cfg(feature = "alloc")]
+pub use ratio_table::RatioTable;
+#[cfg(feature = "tracing")]
+pub use reporter::Reporter;
+#[cfg(feature = "alloc")]
+pub use series::RatioSeries;
+#[cfg(all(feature = "std", target_has_atomic = "64"))]
+pub use sharded::ShardedRatio;
+#[cfg(feature = "sketch")]
+pub use sketch::SketchCounter;
+pub use strict::StrictRatio;
+#[cfg(feature = "alloc")]
+pub use success::SuccessTracker;
+#[cfg(feature = "std")]
+pub use summary::ProportionSummary;
+#[cfg(feature = "std")]
+pub use table::TwoByTwoTable;
+#[cfg(feature = "alloc")]
+pub use windowed::WindowedRatio;
+
+pub trait Count: PrimInt + Unsigned + NumAssign + Debug + Display {}
+
+impl<T: PrimInt + Unsigned + NumAssign + Debug + Display> Count for T {}
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Ord)]
-pub struct CountingRatio {
-    matches: u64,
-    observations: u64,
+/// A `CountingRatio` whose counts are stored in any unsigned primitive integer type.
+/// Smaller types such as `u32` allow memory-dense collections of ratios, while `u128`
+/// suits extremely long-running counts.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericCountingRatio<T: Count = u64> {
+    matches: T,
+    observations: T,
 }
 
-impl CountingRatio {
+pub type CountingRatio = GenericCountingRatio<u64>;
+
+/// Suited to long chains of products, such as Bayesian calculations, whose numerators and
+/// denominators would overflow `u64`.
+pub type BigCountingRatio = GenericCountingRatio<u128>;
+
+impl From<CountingRatio> for BigCountingRatio {
+    fn from(cr: CountingRatio) -> Self {
+        Self::ratio(cr.matches.into(), cr.observations.into())
+    }
+}
+
+impl<T: Count> GenericCountingRatio<T> {
     pub fn new() -> Self {
-        CountingRatio {
-            matches: 0,
-            observations: 0,
+        GenericCountingRatio {
+            matches: T::zero(),
+            observations: T::zero(),
         }
     }
 
-    pub fn ratio(matches: u64, observations: u64) -> Self {
+    /// Builds a ratio from counts as given, without checking them. See `try_ratio()`.
+    pub fn ratio(matches: T, observations: T) -> Self {
         Self {
             matches,
             observations,
         }
     }
 
+    /// Builds a ratio from counts of any integer type, failing if either count does not fit
+    /// in `T` or if there are more matches than observations.
+    ///
+    /// ```
+    /// use counting_ratio::{CountingRatio, GenericCountingRatio, RatioError};
+    ///
+    /// let tests = ["a", "b", "c"];
+    /// assert_eq!(CountingRatio::try_ratio(2, tests.len()), Ok(CountingRatio::ratio(2, 3)));
+    /// assert_eq!(CountingRatio::try_ratio(7, 3), Err(RatioError::MatchesExceedObservations));
+    /// assert_eq!(CountingRatio::try_ratio(-1, 3), Err(RatioError::Overflow));
+    /// assert_eq!(GenericCountingRatio::<u8>::try_ratio(1, 300), Err(RatioError::Overflow));
+    /// ```
+    pub fn try_ratio<M: TryInto<T>, O: TryInto<T>>(
+        matches: M,
+        observations: O,
+    ) -> Result<Self, RatioError> {
+        let matches = matches.try_into().map_err(|_| RatioError::Overflow)?;
+        let observations = observations.try_into().map_err(|_| RatioError::Overflow)?;
+        if matches > observations {
+            Err(RatioError::MatchesExceedObservations)
+        } else {
+            Ok(Self::ratio(matches, observations))
+        }
+    }
+
     pub fn observe(&mut self, condition_met: bool) {
-        self.observations += 1;
+        self.observations += T::one();
         if condition_met {
-            self.matches += 1;
+            self.matches += T::one();
         }
     }
 
     pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
         if prior_condition_met {
-            self.observations += 1;
+            self.observations += T::one();
             if posterior_condition_met {
-                self.matches += 1;
+                self.matches += T::one();
+            }
+        }
+    }
+
+    /// Adds `observations` counted elsewhere, `matches` of which met the condition, such
+    /// as a per-minute aggregate from an upstream system. Panics if `matches` exceeds
+    /// `observations`.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let mut errors = CountingRatio::new();
+    /// errors.observe_batch(3, 1200);
+    /// errors.observe_batch(5, 1100);
+    /// assert_eq!(errors, CountingRatio::ratio(8, 2300));
+    /// ```
+    pub fn observe_batch(&mut self, matches: T, observations: T) {
+        assert!(
+            matches <= observations,
+            "a batch cannot have more matches than observations"
+        );
+        self.matches += matches;
+        self.observations += observations;
+    }
+
+    /// Observes whether an operation succeeded, counting `Ok` as a match.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// fn parse(text: &str, parsed: &mut CountingRatio) -> Result<i32, std::num::ParseIntError> {
+    ///     let value = parsed.track_result(text.parse::<i32>())?;
+    ///     Ok(value * 2)
+    /// }
+    ///
+    /// let mut parsed = CountingRatio::new();
+    /// assert_eq!(parse("21", &mut parsed), Ok(42));
+    /// assert!(parse("twenty", &mut parsed).is_err());
+    /// parsed.observe_some(&"7".parse::<i32>().ok());
+    /// assert_eq!(parsed, CountingRatio::ratio(2, 3));
+    /// ```
+    pub fn observe_result<R, E>(&mut self, result: &Result<R, E>) {
+        self.observe(result.is_ok());
+    }
+
+    /// Observes whether a value was present, counting `Some` as a match.
+    pub fn observe_some<R>(&mut self, option: &Option<R>) {
+        self.observe(option.is_some());
+    }
+
+    /// Observes `result` as `observe_result()` does, then returns it.
+    pub fn track_result<R, E>(&mut self, result: Result<R, E>) -> Result<R, E> {
+        self.observe_result(&result);
+        result
+    }
+
+    /// Observes `option` as `observe_some()` does, then returns it.
+    pub fn track_some<R>(&mut self, option: Option<R>) -> Option<R> {
+        self.observe_some(&option);
+        option
+    }
+
+    /// Observes every flag in `flags`, counting them in bulk rather than one at a time.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let mut passed = CountingRatio::new();
+    /// passed.observe_slice(&[true, false, true, true]);
+    /// assert_eq!(passed, CountingRatio::ratio(3, 4));
+    /// ```
+    pub fn observe_slice(&mut self, flags: &[bool]) {
+        let matches = flags.iter().filter(|flag| **flag).count();
+        self.observe_counts(matches, flags.len());
+    }
+
+    /// Observes every bit in `bits`, counting the set bits by popcount. Requires the
+    /// `bitvec` feature.
+    ///
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let mut passed = CountingRatio::new();
+    /// passed.observe_bits(bits![1, 0, 1, 1, 0, 0, 0, 1]);
+    /// assert_eq!(passed, CountingRatio::ratio(4, 8));
+    /// ```
+    #[cfg(feature = "bitvec")]
+    pub fn observe_bits<S: bitvec::store::BitStore, O: bitvec::order::BitOrder>(
+        &mut self,
+        bits: &bitvec::slice::BitSlice<S, O>,
+    ) {
+        self.observe_counts(bits.count_ones(), bits.len());
+    }
+
+    /// Counts the items of `items` that satisfy `predicate`. The predicate's results are
+    /// packed into 64-bit masks without branching and counted by popcount, a loop that
+    /// compilers readily vectorize.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let latencies: Vec<u32> = (0..1000).collect();
+    /// let slow = CountingRatio::from_filtered_chunks(&latencies, |ms| *ms >= 900);
+    /// assert_eq!(slow, CountingRatio::ratio(100, 1000));
+    /// ```
+    pub fn from_filtered_chunks<I, F: FnMut(&I) -> bool>(items: &[I], predicate: F) -> Self {
+        let mut result = Self::new();
+        result.observe_filtered(items, predicate);
+        result
+    }
+
+    /// Observes every item of `items`, counting those that satisfy `predicate` as
+    /// `from_filtered_chunks()` does.
+    pub fn observe_filtered<I, F: FnMut(&I) -> bool>(&mut self, items: &[I], mut predicate: F) {
+        let mut matches = 0;
+        for chunk in items.chunks(64) {
+            let mut mask = 0_u64;
+            for (i, item) in chunk.iter().enumerate() {
+                mask |= (predicate(item) as u64) << i;
             }
+            matches += mask.count_ones() as usize;
         }
+        self.observe_counts(matches, items.len());
+    }
+
+    fn observe_counts(&mut self, matches: usize, observations: usize) {
+        let convert = |count: usize| T::from(count).expect("count overflows the count type");
+        self.matches += convert(matches);
+        self.observations += convert(observations);
     }
 
     pub fn defined(&self) -> bool {
-        self.observations > 0
+        self.observations > T::zero()
+    }
+
+    pub fn matches(&self) -> T {
+        self.matches
+    }
+
+    pub fn observations(&self) -> T {
+        self.observations
+    }
+
+    /// Observations that did not meet the condition. Saturates at zero should `matches`
+    /// exceed `observations`.
+    pub fn misses(&self) -> T {
+        self.observations.saturating_sub(self.matches)
+    }
+
+    /// The misses among the same observations, such as a failure rate from a success rate.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let passed = CountingRatio::ratio(412, 500);
+    /// assert_eq!(passed.complement(), CountingRatio::ratio(88, 500));
+    /// assert_eq!(passed.complement().complement(), passed);
+    /// assert_eq!(CountingRatio::new().complement(), CountingRatio::new());
+    /// ```
+    pub fn complement(&self) -> Self {
+        Self::ratio(self.misses(), self.observations)
+    }
+
+    /// The odds in favor of the condition as a pair of matches and misses, or `None` if
+    /// undefined.
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # fn main() {
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let wins = CountingRatio::ratio(3, 4);
+    /// assert_eq!(wins.odds(), Some((3, 1)));
+    /// assert!((wins.log_odds().unwrap() - 3.0_f64.ln()).abs() < 1e-12);
+    /// assert_eq!(CountingRatio::ratio(4, 4).log_odds(), None);
+    /// assert_eq!(CountingRatio::new().odds(), None);
+    /// # }
+    /// # #[cfg(not(feature = "std"))]
+    /// # fn main() {}
+    /// ```
+    pub fn odds(&self) -> Option<(T, T)> {
+        if self.defined() {
+            Some((self.matches, self.misses()))
+        } else {
+            None
+        }
+    }
+
+    /// The natural logarithm of the odds in favor of the condition, or `None` if the ratio
+    /// is undefined or the log-odds are infinite because there are no matches or no misses.
+    #[cfg(feature = "std")]
+    pub fn log_odds(&self) -> Option<f64> {
+        let (matches, misses) = self.odds()?;
+        if matches.is_zero() || misses.is_zero() {
+            None
+        } else {
+            Some(matches.to_f64()?.ln() - misses.to_f64()?.ln())
+        }
+    }
+
+    /// The proportion of observations that met the condition; `NaN` if undefined.
+    pub fn rate(&self) -> f64 {
+        f64::from(*self)
+    }
+
+    /// The proportion of observations that met the condition, or `None` if undefined.
+    pub fn value(&self) -> Option<f64> {
+        if self.defined() {
+            Some(self.rate())
+        } else {
+            None
+        }
+    }
+
+    pub fn value_or(&self, default: f64) -> f64 {
+        self.value().unwrap_or(default)
+    }
+
+    /// The proportion in parts per hundred, or `None` if undefined.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let defects = CountingRatio::ratio(3, 1000);
+    /// assert_eq!(defects.percent(), Some(0.3));
+    /// assert_eq!(defects.per_mille(), Some(3.0));
+    /// assert_eq!(defects.basis_points(), Some(30.0));
+    /// assert_eq!(defects.ppm(), Some(3000.0));
+    /// assert_eq!(CountingRatio::new().ppm(), None);
+    /// ```
+    pub fn percent(&self) -> Option<f64> {
+        self.scaled_value(100.0)
+    }
+
+    /// The proportion in parts per thousand, or `None` if undefined.
+    pub fn per_mille(&self) -> Option<f64> {
+        self.scaled_value(1_000.0)
+    }
+
+    /// The proportion in parts per ten thousand, or `None` if undefined.
+    pub fn basis_points(&self) -> Option<f64> {
+        self.scaled_value(10_000.0)
+    }
+
+    /// The proportion in parts per million, or `None` if undefined.
+    pub fn ppm(&self) -> Option<f64> {
+        self.scaled_value(1_000_000.0)
+    }
+
+    // Scales the counts before dividing, so that e.g. 3/1000 is exactly 0.3 percent.
+    fn scaled_value(&self, scale: f64) -> Option<f64> {
+        let (matches, observations) = self.counts_f64();
+        self.defined().then(|| matches * scale / observations)
+    }
+
+    /// Retracts a single earlier observation. Panics if no such observation was recorded.
+    pub fn unobserve(&mut self, condition_met: bool) {
+        let matches = if condition_met { T::one() } else { T::zero() };
+        *self -= Self::ratio(matches, T::one());
+    }
+
+    /// Retracts the observations in `rhs`, returning `None` if `rhs` contains more matches
+    /// or more misses than `self`.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let matches = self.matches.checked_sub(&rhs.matches)?;
+        let observations = self.observations.checked_sub(&rhs.observations)?;
+        if matches > observations {
+            None
+        } else {
+            Some(Self::ratio(matches, observations))
+        }
+    }
+
+    /// Multiplies both the matches and the observations by `factor`, leaving the
+    /// proportion unchanged while weighting the ratio `factor` times as heavily when it is
+    /// added to others. Panics if either count overflows.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let shard = CountingRatio::ratio(2, 5);
+    /// assert_eq!(shard.scale(3), CountingRatio::ratio(6, 15));
+    /// assert_eq!(shard.scale_matches(2), Some(CountingRatio::ratio(4, 5)));
+    /// assert_eq!(shard.scale_matches(3), None);
+    /// assert_eq!(CountingRatio::ratio(2, u64::MAX).scale_matches(1 << 63), None);
+    /// ```
+    pub fn scale(self, factor: T) -> Self {
+        let scaled = |count: T| count.checked_mul(&factor).expect("scaled count overflows");
+        Self::ratio(scaled(self.matches), scaled(self.observations))
+    }
+
+    /// Multiplies only the matches by `factor`, which changes the proportion. Returns
+    /// `None` if the result would overflow or have more matches than observations.
+    pub fn scale_matches(self, factor: T) -> Option<Self> {
+        let matches = self.matches.checked_mul(&factor)?;
+        if matches > self.observations {
+            None
+        } else {
+            Some(Self::ratio(matches, self.observations))
+        }
+    }
+
+    /// The matches and observations divided by their greatest common divisor, leaving the
+    /// counts themselves untouched. An empty ratio reduces to `(0, 0)`.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let correct = CountingRatio::ratio(18, 24);
+    /// assert_eq!(correct.as_reduced(), (3, 4));
+    /// assert_eq!(correct.reduced(), CountingRatio::ratio(3, 4));
+    /// assert_eq!(correct.observations(), 24);
+    /// assert_eq!(CountingRatio::ratio(0, 5).as_reduced(), (0, 1));
+    /// ```
+    pub fn as_reduced(&self) -> (T, T) {
+        let common = gcd(self.matches, self.observations);
+        if common.is_zero() {
+            (self.matches, self.observations)
+        } else {
+            (self.matches / common, self.observations / common)
+        }
+    }
+
+    /// The same proportion as `as_reduced()`, as a ratio. Note that it counts fewer
+    /// observations than the original.
+    pub fn reduced(&self) -> Self {
+        let (matches, observations) = self.as_reduced();
+        Self::ratio(matches, observations)
+    }
+
+    /// Converts the counts to another count type, returning `None` if either count
+    /// does not fit.
+    pub fn cast<U: Count>(self) -> Option<GenericCountingRatio<U>> {
+        Some(GenericCountingRatio {
+            matches: U::from(self.matches)?,
+            observations: U::from(self.observations)?,
+        })
+    }
+
+    fn counts_f64(&self) -> (f64, f64) {
+        (
+            self.matches.to_f64().unwrap_or(f64::NAN),
+            self.observations.to_f64().unwrap_or(f64::NAN),
+        )
+    }
+
+    /// Returns the Wilson score interval around the observed proportion, where `z` is the
+    /// standard normal quantile for the desired confidence (e.g. 1.96 for 95%). An undefined
+    /// ratio yields the uninformative interval `(0.0, 1.0)`.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let (low, high) = CountingRatio::ratio(15, 100).wilson_interval(1.96);
+    /// assert!((low - 0.0930).abs() < 1e-4);
+    /// assert!((high - 0.2328).abs() < 1e-4);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn wilson_interval(&self, z: f64) -> (f64, f64) {
+        if !self.defined() {
+            return (0.0, 1.0);
+        }
+        let (_, n) = self.counts_f64();
+        let p = f64::from(*self);
+        let z2 = z * z;
+        let denominator = 1.0 + z2 / n;
+        let center = (p + z2 / (2.0 * n)) / denominator;
+        let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+
+    /// Returns the Clopper–Pearson exact interval around the observed proportion at the
+    /// given `confidence` level (e.g. 0.95). It is conservative, but unlike approximate
+    /// intervals remains trustworthy for small numbers of observations. An undefined ratio
+    /// yields `(0.0, 1.0)`.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let (low, high) = CountingRatio::ratio(15, 100).exact_interval(0.95);
+    /// assert!((low - 0.08645).abs() < 1e-5);
+    /// assert!((high - 0.23531).abs() < 1e-5);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn exact_interval(&self, confidence: f64) -> (f64, f64) {
+        if !self.defined() {
+            return (0.0, 1.0);
+        }
+        let (x, n) = self.counts_f64();
+        let tail = (1.0 - confidence) / 2.0;
+        let low = if x == 0.0 {
+            0.0
+        } else {
+            special::beta_quantile(tail, x, n - x + 1.0)
+        };
+        let high = if x >= n {
+            1.0
+        } else {
+            special::beta_quantile(1.0 - tail, x + 1.0, n - x)
+        };
+        (low, high)
+    }
+
+    /// The difference between this ratio's proportion and that of `other`, or `None` if
+    /// either is undefined.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let variant = CountingRatio::ratio(30, 200);
+    /// let control = CountingRatio::ratio(20, 200);
+    /// assert!((variant.absolute_difference(&control).unwrap() - 0.05).abs() < 1e-12);
+    /// assert!((variant.lift(&control).unwrap() - 1.5).abs() < 1e-12);
+    /// assert!((variant.relative_difference(&control).unwrap() - 0.5).abs() < 1e-12);
+    ///
+    /// assert_eq!(variant.lift(&CountingRatio::ratio(0, 200)), None);
+    /// assert_eq!(variant.absolute_difference(&CountingRatio::new()), None);
+    /// ```
+    pub fn absolute_difference(&self, other: &Self) -> Option<f64> {
+        Some(self.value()? - other.value()?)
+    }
+
+    /// This ratio's proportion as a multiple of that of `other`. Returns `None` if either
+    /// is undefined or `other` has no matches.
+    pub fn lift(&self, other: &Self) -> Option<f64> {
+        let baseline = other.value()?;
+        if baseline == 0.0 {
+            None
+        } else {
+            Some(self.value()? / baseline)
+        }
+    }
+
+    /// The change from the proportion of `other` to this ratio's, as a fraction of the
+    /// former. Returns `None` if either is undefined or `other` has no matches.
+    pub fn relative_difference(&self, other: &Self) -> Option<f64> {
+        Some(self.lift(other)? - 1.0)
     }
 }
 
-impl From<CountingRatio> for f64 {
-    fn from(cr: CountingRatio) -> Self {
-        cr.matches as f64 / cr.observations as f64
+impl<T: Count> Default for GenericCountingRatio<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl Display for CountingRatio {
+impl<T: Count> From<GenericCountingRatio<T>> for f64 {
+    fn from(cr: GenericCountingRatio<T>) -> Self {
+        let (matches, observations) = cr.counts_f64();
+        matches / observations
+    }
+}
+
+/// The formatter's precision sets the number of decimal places in the percentage, which
+/// defaults to two. The alternate form `{:#}` renders only the percentage.
+impl<T: Count> Display for GenericCountingRatio<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        write!(
-            f,
-            "{}/{} ({:.2}%)",
-            self.matches,
-            self.observations,
-            100.0 * f64::from(*self)
-        )
+        let precision = f.precision().unwrap_or(2);
+        let percent = 100.0 * f64::from(*self);
+        if f.alternate() {
+            write!(f, "{percent:.precision$}%")
+        } else {
+            write!(
+                f,
+                "{}/{} ({percent:.precision$}%)",
+                self.matches, self.observations
+            )
+        }
+    }
+}
+
+impl<T: Count> FromIterator<bool> for GenericCountingRatio<T> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
     }
 }
 
-impl Add for CountingRatio {
-    type Output = CountingRatio;
+impl<T: Count> Extend<bool> for GenericCountingRatio<T> {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for condition_met in iter {
+            self.observe(condition_met);
+        }
+    }
+}
+
+/// Measures a `CountingRatio` over the items of any iterator in a single chained call.
+pub trait CountRatioIterExt: Iterator + Sized {
+    fn count_ratio<P: FnMut(&Self::Item) -> bool>(self, mut condition: P) -> CountingRatio {
+        self.map(|item| condition(&item)).collect()
+    }
+
+    fn count_ratio_with_prior<P, Q>(self, mut prior: P, mut posterior: Q) -> CountingRatio
+    where
+        P: FnMut(&Self::Item) -> bool,
+        Q: FnMut(&Self::Item) -> bool,
+    {
+        let mut result = CountingRatio::new();
+        for item in self {
+            if prior(&item) {
+                result.observe(posterior(&item));
+            }
+        }
+        result
+    }
+}
+
+impl<I: Iterator> CountRatioIterExt for I {}
+
+impl<T: Count> Add for GenericCountingRatio<T> {
+    type Output = GenericCountingRatio<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut result = self;
@@ -181,155 +1116,380 @@ impl Add for CountingRatio {
     }
 }
 
-impl AddAssign for CountingRatio {
+/// Every arithmetic operator also accepts references.
+///
+/// ```
+/// use counting_ratio::CountingRatio;
+/// use std::collections::BTreeMap;
+///
+/// let shards = BTreeMap::from([
+///     ("east", CountingRatio::ratio(1, 4)),
+///     ("west", CountingRatio::ratio(2, 6)),
+/// ]);
+/// let mut total = CountingRatio::new();
+/// for ratio in shards.values() {
+///     total += ratio;
+/// }
+/// assert_eq!(total, &shards["east"] + &shards["west"]);
+/// assert_eq!(&total - &shards["west"], shards["east"]);
+/// ```
+impl<T: Count> AddAssign for GenericCountingRatio<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.matches += rhs.matches;
         self.observations += rhs.observations;
     }
 }
 
-impl Mul for CountingRatio {
-    type Output = CountingRatio;
+impl<T: Count> Sub for GenericCountingRatio<T> {
+    type Output = GenericCountingRatio<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result -= rhs;
+        result
+    }
+}
+
+impl<T: Count> SubAssign for GenericCountingRatio<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self
+            .checked_sub(rhs)
+            .expect("cannot retract observations that were never made");
+    }
+}
 
-    fn mul(self, rhs: CountingRatio) -> Self::Output {
+impl<T: Count> Mul for GenericCountingRatio<T> {
+    type Output = GenericCountingRatio<T>;
+
+    fn mul(self, rhs: GenericCountingRatio<T>) -> Self::Output {
         let mut result = self;
         result *= rhs;
         result
     }
 }
 
-impl MulAssign for CountingRatio {
-    fn mul_assign(&mut self, rhs: CountingRatio) {
+impl<T: Count> MulAssign for GenericCountingRatio<T> {
+    fn mul_assign(&mut self, rhs: GenericCountingRatio<T>) {
         self.matches *= rhs.matches;
         self.observations *= rhs.observations;
     }
 }
 
-impl Mul<u64> for CountingRatio {
-    type Output = CountingRatio;
+/// Multiplying by a count scales the matches and the observations alike, as `scale()` does,
+/// so that `ratio * 3` equals `ratio + ratio + ratio`.
+///
+/// ```
+/// use counting_ratio::CountingRatio;
+///
+/// let shard = CountingRatio::ratio(2, 5);
+/// assert_eq!(shard * 3, shard + shard + shard);
+/// ```
+impl<T: Count> Mul<T> for GenericCountingRatio<T> {
+    type Output = GenericCountingRatio<T>;
 
-    fn mul(self, rhs: u64) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         let mut result = self;
         result *= rhs;
         result
     }
 }
 
-impl Div for CountingRatio {
-    type Output = CountingRatio;
+impl<T: Count> Div for GenericCountingRatio<T> {
+    type Output = GenericCountingRatio<T>;
 
-    fn div(self, rhs: CountingRatio) -> Self::Output {
+    fn div(self, rhs: GenericCountingRatio<T>) -> Self::Output {
         let mut result = self;
         result /= rhs;
         result
     }
 }
 
-impl DivAssign for CountingRatio {
+impl<T: Count> DivAssign for GenericCountingRatio<T> {
     fn div_assign(&mut self, rhs: Self) {
         self.matches *= rhs.observations;
         self.observations *= rhs.matches;
     }
 }
 
-impl MulAssign<u64> for CountingRatio {
-    fn mul_assign(&mut self, rhs: u64) {
-        self.matches *= rhs;
+impl<T: Count> MulAssign<T> for GenericCountingRatio<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = self.scale(rhs);
     }
 }
 
-impl PartialOrd for CountingRatio {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if self.matches == 0 && other.matches == 0 {
-            Some(Ordering::Equal)
-        } else if self.matches == 0 {
-            Some(Ordering::Less)
-        } else if other.matches == 0 {
-            Some(Ordering::Greater)
-        } else if self.observations == other.observations {
-            self.observations.partial_cmp(&other.observations)
-        } else {
-            (self.matches * other.observations).partial_cmp(&(other.matches * self.observations))
+// Implements each operator for references too, as the standard numeric types do, so that
+// ratios held behind references combine without explicit dereferencing.
+macro_rules! forward_ref_ops {
+    ($($op:ident $method:ident $op_assign:ident $method_assign:ident),*) => {$(
+        impl<T: Count> $op<&GenericCountingRatio<T>> for GenericCountingRatio<T> {
+            type Output = GenericCountingRatio<T>;
+
+            fn $method(self, rhs: &GenericCountingRatio<T>) -> Self::Output {
+                $op::$method(self, *rhs)
+            }
+        }
+
+        impl<T: Count> $op<GenericCountingRatio<T>> for &GenericCountingRatio<T> {
+            type Output = GenericCountingRatio<T>;
+
+            fn $method(self, rhs: GenericCountingRatio<T>) -> Self::Output {
+                $op::$method(*self, rhs)
+            }
+        }
+
+        impl<T: Count> $op<&GenericCountingRatio<T>> for &GenericCountingRatio<T> {
+            type Output = GenericCountingRatio<T>;
+
+            fn $method(self, rhs: &GenericCountingRatio<T>) -> Self::Output {
+                $op::$method(*self, *rhs)
+            }
         }
+
+        impl<T: Count> $op_assign<&GenericCountingRatio<T>> for GenericCountingRatio<T> {
+            fn $method_assign(&mut self, rhs: &GenericCountingRatio<T>) {
+                $op_assign::$method_assign(self, *rhs);
+            }
+        }
+    )*};
+}
+
+forward_ref_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign
+);
+
+/// Pools the observations of every ratio, as when combining per-shard counters.
+///
+/// ```
+/// use counting_ratio::CountingRatio;
+///
+/// let shards = vec![CountingRatio::ratio(1, 4), CountingRatio::ratio(2, 6)];
+/// let total: CountingRatio = shards.iter().sum();
+/// assert_eq!(total, CountingRatio::ratio(3, 10));
+/// assert_eq!(Vec::<CountingRatio>::new().into_iter().sum::<CountingRatio>(), CountingRatio::new());
+///
+/// let joint: CountingRatio = shards.into_iter().product();
+/// assert_eq!(joint, CountingRatio::ratio(2, 24));
+/// ```
+impl<T: Count> Sum for GenericCountingRatio<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(), |total, ratio| total + ratio)
+    }
+}
+
+impl<'a, T: Count> Sum<&'a GenericCountingRatio<T>> for GenericCountingRatio<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
     }
 }
 
-trait_set! {
-    pub trait Countable = Copy + Ord + Debug;
+/// Multiplies the ratios as `Mul` does, starting from 1/1.
+impl<T: Count> Product for GenericCountingRatio<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ratio(T::one(), T::one()), |total, ratio| {
+            total * ratio
+        })
+    }
 }
 
-pub struct BayesianCounter<L: Countable, S: Countable> {
-    counts: BTreeMap<L, BTreeMap<S, u64>>,
-    total: u64,
+impl<'a, T: Count> Product<&'a GenericCountingRatio<T>> for GenericCountingRatio<T> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
 }
 
-impl<L: Countable, S: Countable> BayesianCounter<L, S> {
-    pub fn new() -> Self {
-        Self {
-            counts: BTreeMap::new(),
-            total: 0,
+impl<T: Count> PartialOrd for GenericCountingRatio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Ratios are ordered by their mathematical value. Undefined ratios precede all defined
+/// ratios. Because equality is structural, mathematically equal ratios such as 3/10 and
+/// 6/20 are ordered by their number of observations.
+impl<T: Count> Ord for GenericCountingRatio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.defined(), other.defined()) {
+            (false, false) => self.matches.cmp(&other.matches),
+            _ => self
+                .cmp_value(other)
+                .then(self.observations.cmp(&other.observations)),
         }
     }
+}
 
-    pub fn observe(&mut self, example: S, label: L) {
-        match self.counts.get_mut(&label) {
-            Some(counter) => {
-                bump!(counter, example);
-            }
-            None => {
-                let mut counter = BTreeMap::new();
-                bump!(counter, example);
-                self.counts.insert(label, counter);
-            }
-        };
-        self.total += 1;
+impl<T: Count> GenericCountingRatio<T> {
+    // Compares mathematical values only, so that 3/10 and 6/20 are equal. Undefined ratios
+    // precede all defined ratios.
+    pub(crate) fn cmp_value(&self, other: &Self) -> Ordering {
+        match (self.defined(), other.defined()) {
+            (false, false) => Ordering::Equal,
+            (false, true) => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (true, true) => compare_fractions(
+                self.matches,
+                self.observations,
+                other.matches,
+                other.observations,
+            ),
+        }
     }
+}
 
-    pub fn count(&self, example: S, label: L) -> u64 {
-        self.counts
-            .get(&label)
-            .map_or(0, |t| t.get(&example).copied().unwrap_or(0))
+/// A defined ratio equals a threshold when its value is exactly the threshold, read as
+/// `PartialOrd<f64>` reads it. An undefined ratio, like NaN, equals nothing.
+impl<T: Count> PartialEq<f64> for GenericCountingRatio<T> {
+    fn eq(&self, threshold: &f64) -> bool {
+        self.partial_cmp(threshold) == Some(Ordering::Equal)
     }
+}
 
-    pub fn label_count(&self, label: L) -> u64 {
-        self.counts.get(&label).map_or(0, |t| t.values().sum())
+/// Compares a ratio's value with a threshold without rounding the ratio to `f64`. The
+/// threshold is read as the shortest decimal that rounds to it, which is the decimal written
+/// in the source, so that 1/10 equals `0.1` even though the nearest `f64` lies slightly above
+/// one tenth. Thresholds with too many decimal places to read this way are compared by
+/// their exact binary value. Undefined ratios and NaN thresholds are unordered.
+///
+/// ```
+/// use counting_ratio::CountingRatio;
+///
+/// let uptime = CountingRatio::ratio(19, 20);
+/// assert!(uptime >= 0.95 && uptime == 0.95);
+/// assert!(CountingRatio::ratio(1, 10) == 0.1);
+/// assert!(CountingRatio::ratio(1, 3) > 0.3333333333333333);
+/// assert!(CountingRatio::ratio(7, 2) < f64::INFINITY);
+/// assert!(!(CountingRatio::new() >= 0.0));
+/// assert!(CountingRatio::ratio(1, 2) > -0.0);
+/// assert!(CountingRatio::ratio(0, 2) == -0.0);
+/// ```
+impl<T: Count> PartialOrd<f64> for GenericCountingRatio<T> {
+    fn partial_cmp(&self, threshold: &f64) -> Option<Ordering> {
+        if !self.defined() || threshold.is_nan() {
+            None
+        } else if *threshold < 0.0 {
+            Some(Ordering::Greater)
+        } else if *threshold == 0.0 {
+            // Also covers -0.0, which formats with a sign.
+            Some(self.matches.cmp(&T::zero()))
+        } else if *threshold >= u128::MAX as f64 {
+            // No ratio of counts that fit in a u128 can reach 2^128.
+            Some(Ordering::Less)
+        } else {
+            let (matches, observations) = (self.matches.to_u128()?, self.observations.to_u128()?);
+            Some(match shortest_decimal(*threshold) {
+                Some((numerator, denominator)) => {
+                    compare_fractions(matches, observations, numerator, denominator)
+                }
+                None => compare_to_float(matches, observations, *threshold),
+            })
+        }
     }
+}
 
-    pub fn example_count(&self, example: S) -> u64 {
-        self.counts
-            .keys()
-            .map(|label| self.count(example, *label))
-            .sum()
+// The shortest decimal that rounds to the finite, non-negative `x`, as a fraction with a
+// power of ten as its denominator, or `None` if either part would not fit in a u128.
+fn shortest_decimal(x: f64) -> Option<(u128, u128)> {
+    use core::fmt::Write;
+
+    struct Buffer {
+        bytes: [u8; 32],
+        len: usize,
     }
 
-    pub fn p_label(&self, label: L) -> CountingRatio {
-        CountingRatio::ratio(self.label_count(label), self.total)
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(core::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
     }
 
-    pub fn p_example(&self, example: S) -> CountingRatio {
-        CountingRatio::ratio(self.example_count(example), self.total)
+    let mut buffer = Buffer {
+        bytes: [0; 32],
+        len: 0,
+    };
+    write!(buffer, "{x:e}").ok()?;
+    let text = core::str::from_utf8(&buffer.bytes[..buffer.len]).ok()?;
+    let (mantissa, exponent) = text.split_once('e')?;
+    let exponent: i32 = exponent.parse().ok()?;
+    let mut digits = 0u128;
+    let mut places = 0;
+    for (index, byte) in mantissa.bytes().filter(|b| *b != b'.').enumerate() {
+        let digit = char::from(byte).to_digit(10)?;
+        digits = digits.checked_mul(10)?.checked_add(u128::from(digit))?;
+        places = index as i32;
     }
+    let shift = exponent - places;
+    if shift >= 0 {
+        Some((digits.checked_mul(10u128.checked_pow(shift as u32)?)?, 1))
+    } else {
+        Some((digits, 10u128.checked_pow(shift.unsigned_abs())?))
+    }
+}
 
-    pub fn p_example_given_label(&self, example: S, label: L) -> CountingRatio {
-        CountingRatio::ratio(self.count(example, label), self.label_count(label))
+// Compares a/b with the non-negative x, which lies below 2^128, exactly. After the integer
+// parts, every finite f64 has a terminating binary expansion, so the fractional parts are
+// compared one binary digit at a time.
+fn compare_to_float(a: u128, b: u128, x: f64) -> Ordering {
+    let whole = num_traits::float::FloatCore::floor(x);
+    let integer = whole as u128;
+    let quotient = a / b;
+    if quotient != integer {
+        return quotient.cmp(&integer);
+    }
+    let (mut remainder, mut fraction) = (a % b, x - whole);
+    while fraction > 0.0 {
+        if remainder == 0 {
+            return Ordering::Less;
+        }
+        // Doubles the remainder without overflowing: 2r >= b exactly when r >= b - r.
+        let digit = remainder >= b - remainder;
+        remainder = if digit {
+            remainder - (b - remainder)
+        } else {
+            remainder + remainder
+        };
+        fraction *= 2.0;
+        if digit != (fraction >= 1.0) {
+            return if digit {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+        if digit {
+            fraction -= 1.0;
+        }
     }
+    remainder.cmp(&0)
+}
 
-    pub fn p_label_given_example(&self, label: L, example: S) -> CountingRatio {
-        self.p_example_given_label(example, label) * self.p_label(label) / self.p_example(example)
+// Compares a/b with c/d without cross-multiplying, so that no count can overflow.
+// Whenever the integer parts match, comparing the fractional remainders r1/b and r2/d
+// is equivalent to comparing their reciprocals d/r2 and b/r1 in reverse.
+fn compare_fractions<T: Count>(mut a: T, mut b: T, mut c: T, mut d: T) -> Ordering {
+    loop {
+        let (q1, q2) = (a / b, c / d);
+        if q1 != q2 {
+            return q1.cmp(&q2);
+        }
+        let (r1, r2) = (a % b, c % d);
+        if r1.is_zero() || r2.is_zero() {
+            return r1.cmp(&r2);
+        }
+        (a, b, c, d) = (d, r2, b, r1);
     }
+}
 
-    pub fn label_ranking_for(&self, example: S) -> Vec<L> {
-        let mut result: Vec<(CountingRatio, L)> = self
-            .counts
-            .keys()
-            .map(|label| {
-                (
-                    self.p_example_given_label(example, *label) * self.label_count(*label),
-                    *label,
-                )
-            })
-            .collect();
-        result.sort();
-        result.iter().map(|(_, label)| *label).collect()
+pub(crate) fn gcd<T: Count>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
     }
+    a
 }
@@ -0,0 +1,166 @@
+//! Renders ratios by the number conventions of a locale. A `LocaleFormat` sets the thousands
+//! separator of the counts, the decimal separator of the percentage, and whether a space
+//! precedes the percent sign. `localized()` then displays a ratio in that format, honoring
+//! the precision and the alternate form just as `Display` does.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, LocaleFormat};
+//!
+//! let german = LocaleFormat::new()
+//!     .with_thousands_separator('.')
+//!     .with_decimal_separator(',')
+//!     .with_space_before_percent(true);
+//! let conversions = CountingRatio::ratio(1_234_567, 2_000_000);
+//! assert_eq!(
+//!     format!("{}", conversions.localized(german)),
+//!     "1.234.567/2.000.000 (61,73 %)"
+//! );
+//! assert_eq!(format!("{:#.1}", conversions.localized(german)), "61,7 %");
+//!
+//! let english = LocaleFormat::new().with_thousands_separator(',');
+//! assert_eq!(
+//!     format!("{}", conversions.localized(english)),
+//!     "1,234,567/2,000,000 (61.73%)"
+//! );
+//! assert_eq!(
+//!     format!("{}", conversions.localized(LocaleFormat::new())),
+//!     format!("{conversions}")
+//! );
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+use core::fmt::{Display, Formatter, Write};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocaleFormat {
+    thousands_separator: Option<char>,
+    decimal_separator: char,
+    space_before_percent: bool,
+}
+
+impl LocaleFormat {
+    /// The format of `Display`: no thousands separator, a decimal point, and no space
+    /// before the percent sign.
+    pub fn new() -> Self {
+        Self {
+            thousands_separator: None,
+            decimal_separator: '.',
+            space_before_percent: false,
+        }
+    }
+
+    /// Separates each group of three digits in the counts with `separator`.
+    pub fn with_thousands_separator(self, separator: char) -> Self {
+        Self {
+            thousands_separator: Some(separator),
+            ..self
+        }
+    }
+
+    pub fn with_decimal_separator(self, separator: char) -> Self {
+        Self {
+            decimal_separator: separator,
+            ..self
+        }
+    }
+
+    pub fn with_space_before_percent(self, space: bool) -> Self {
+        Self {
+            space_before_percent: space,
+            ..self
+        }
+    }
+
+    fn write_count<T: Count>(&self, f: &mut Formatter<'_>, count: T) -> core::fmt::Result {
+        let Some(separator) = self.thousands_separator else {
+            return write!(f, "{count}");
+        };
+        // The least significant digit comes first; a u128 has at most 39 digits.
+        let mut digits = [0u8; 39];
+        let mut len = 0;
+        let mut rest = count.to_u128().unwrap_or_default();
+        loop {
+            digits[len] = (rest % 10) as u8;
+            rest /= 10;
+            len += 1;
+            if rest == 0 {
+                break;
+            }
+        }
+        for (place, digit) in digits[..len].iter().enumerate().rev() {
+            f.write_char(char::from(b'0' + digit))?;
+            if place > 0 && place % 3 == 0 {
+                f.write_char(separator)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for LocaleFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A ratio displayed in a `LocaleFormat`, as returned by `localized()`.
+#[derive(Copy, Clone, Debug)]
+pub struct LocalizedRatio<T: Count = u64> {
+    ratio: GenericCountingRatio<T>,
+    format: LocaleFormat,
+}
+
+impl<T: Count> GenericCountingRatio<T> {
+    pub fn localized(&self, format: LocaleFormat) -> LocalizedRatio<T> {
+        LocalizedRatio {
+            ratio: *self,
+            format,
+        }
+    }
+}
+
+impl<T: Count> Display for LocalizedRatio<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let percent = 100.0 * f64::from(self.ratio);
+        let alternate = f.alternate();
+        if !alternate {
+            self.format.write_count(f, self.ratio.matches())?;
+            f.write_char('/')?;
+            self.format.write_count(f, self.ratio.observations())?;
+            f.write_str(" (")?;
+        }
+        write!(
+            DecimalSeparator {
+                f: &mut *f,
+                separator: self.format.decimal_separator,
+            },
+            "{percent:.precision$}"
+        )?;
+        if self.format.space_before_percent {
+            f.write_char(' ')?;
+        }
+        f.write_char('%')?;
+        if !alternate {
+            f.write_char(')')?;
+        }
+        Ok(())
+    }
+}
+
+// Passes formatted text through, replacing the decimal point with `separator`.
+struct DecimalSeparator<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    separator: char,
+}
+
+impl Write for DecimalSeparator<'_, '_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            self.f
+                .write_char(if c == '.' { self.separator } else { c })?;
+        }
+        Ok(())
+    }
+}
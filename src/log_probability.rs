@@ -0,0 +1,173 @@
+//! A `LogProbability` keeps the natural logarithms of the numerator and denominator of a
+//! probability. Multiplying probabilities adds their logarithms, so long chains of evidence
+//! never overflow the way products of `CountingRatio`s eventually must.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, LogProbability};
+//!
+//! let mut chain = LogProbability::from(CountingRatio::ratio(1, 1));
+//! for _ in 0..20 {
+//!     chain *= CountingRatio::ratio(999, 1_000_000).into();
+//! }
+//!
+//! assert!((chain.ln() - 20.0 * (999.0f64 / 1_000_000.0).ln()).abs() < 1e-9);
+//! assert!(chain.value() > 0.0);
+//! ```
+//!
+//! `BayesianCounter` uses it to compute posteriors given several pieces of evidence, each
+//! assumed independent of the others given the label.
+//!
+//! ```
+//! use counting_ratio::BayesianCounter;
+//!
+//! let mut bayesian = BayesianCounter::with_smoothing(1);
+//! for (words, label) in [("buy cheap now", "spam"), ("see you soon", "ham"), ("buy now", "spam")] {
+//!     for word in words.split(' ') {
+//!         bayesian.observe(word, label);
+//!     }
+//! }
+//!
+//! let posterior = bayesian.log_posterior(&["cheap", "now"]);
+//! assert_eq!(posterior[1].0, "spam");
+//! assert!(posterior[1].1.value() > 0.85);
+//! assert!((posterior[0].1.value() + posterior[1].1.value() - 1.0).abs() < 1e-12);
+//! ```
+
+use crate::{BayesianCounter, Countable, CountingRatio};
+use core::cmp::Ordering;
+use core::ops::{Div, DivAssign, Mul, MulAssign};
+
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogProbability {
+    log_numerator: f64,
+    log_denominator: f64,
+}
+
+impl LogProbability {
+    /// The probability whose natural logarithm is `ln`.
+    pub fn from_ln(ln: f64) -> Self {
+        Self {
+            log_numerator: ln,
+            log_denominator: 0.0,
+        }
+    }
+
+    /// The sum of the natural logarithms of every numerator multiplied into this probability.
+    pub fn log_numerator(&self) -> f64 {
+        self.log_numerator
+    }
+
+    /// The sum of the natural logarithms of every denominator multiplied into this
+    /// probability.
+    pub fn log_denominator(&self) -> f64 {
+        self.log_denominator
+    }
+
+    pub fn ln(&self) -> f64 {
+        self.log_numerator - self.log_denominator
+    }
+
+    /// The probability itself, which may underflow to zero when `ln()` is very negative.
+    pub fn value(&self) -> f64 {
+        self.ln().exp()
+    }
+}
+
+/// Converts the counts of `ratio` to logarithms. An undefined ratio yields `NaN`.
+impl From<CountingRatio> for LogProbability {
+    fn from(ratio: CountingRatio) -> Self {
+        if ratio.defined() {
+            Self {
+                log_numerator: (ratio.matches() as f64).ln(),
+                log_denominator: (ratio.observations() as f64).ln(),
+            }
+        } else {
+            Self::from_ln(f64::NAN)
+        }
+    }
+}
+
+impl Mul for LogProbability {
+    type Output = LogProbability;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result *= rhs;
+        result
+    }
+}
+
+impl MulAssign for LogProbability {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.log_numerator += rhs.log_numerator;
+        self.log_denominator += rhs.log_denominator;
+    }
+}
+
+impl Div for LogProbability {
+    type Output = LogProbability;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result /= rhs;
+        result
+    }
+}
+
+impl DivAssign for LogProbability {
+    fn div_assign(&mut self, rhs: Self) {
+        self.log_numerator += rhs.log_denominator;
+        self.log_denominator += rhs.log_numerator;
+    }
+}
+
+/// Probabilities are compared by value, regardless of how their logarithms were accumulated.
+impl PartialEq for LogProbability {
+    fn eq(&self, other: &Self) -> bool {
+        self.ln() == other.ln()
+    }
+}
+
+impl PartialOrd for LogProbability {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.ln().partial_cmp(&other.ln())
+    }
+}
+
+impl<L: Countable, S: Countable> BayesianCounter<L, S> {
+    /// `p(label) * p(e_1 | label) * ... * p(e_n | label)` for the examples in `evidence`,
+    /// which is proportional to `p(label | evidence)`.
+    pub fn log_score(&self, evidence: &[S], label: L) -> LogProbability {
+        let prior = LogProbability::from(self.p_label(label.clone()));
+        evidence
+            .iter()
+            .map(|example| LogProbability::from(self.p_example_given_label_of(example, &label)))
+            .fold(prior, |score, p| score * p)
+    }
+
+    /// Returns `p(label | evidence)` for every label, in ascending order of label. The
+    /// probabilities are normalized in log space, so that they sum to one even when every
+    /// unnormalized score underflows `f64`. If no label has a nonzero score, every
+    /// probability is `NaN`.
+    pub fn log_posterior(&self, evidence: &[S]) -> Vec<(L, LogProbability)> {
+        let scores: Vec<(L, f64)> = self
+            .labels()
+            .map(|label| (label.clone(), self.log_score(evidence, label).ln()))
+            .collect();
+        let max = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let log_normalizer = max
+            + scores
+                .iter()
+                .map(|(_, score)| (score - max).exp())
+                .sum::<f64>()
+                .ln();
+        scores
+            .into_iter()
+            .map(|(label, score)| (label, LogProbability::from_ln(score - log_normalizer)))
+            .collect()
+    }
+}
@@ -0,0 +1,129 @@
+//! A `TransitionCounter` is the sequential sibling of `BayesianCounter`. It counts
+//! transitions between consecutive states, estimating the transition probabilities of a
+//! Markov chain.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use counting_ratio::{CountingRatio, TransitionCounter};
+//!
+//! let mut weather = TransitionCounter::new();
+//! for day in ["sun", "sun", "rain", "sun", "sun", "sun", "rain", "rain", "sun"] {
+//!     weather.observe(day);
+//! }
+//!
+//! assert_eq!(weather.p_next_given_current("rain", "sun"), CountingRatio::ratio(2, 5));
+//! assert_eq!(weather.p_next_given_current("sun", "rain"), CountingRatio::ratio(2, 3));
+//!
+//! let stationary = weather.stationary_distribution();
+//! assert_eq!(stationary[0].0, "rain");
+//! assert!((stationary[0].1 - 0.375).abs() < 1e-9);
+//! assert!((stationary[1].1 - 0.625).abs() < 1e-9);
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use crate::{BayesianCounter, Countable, CountingRatio};
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+pub struct TransitionCounter<S: Countable> {
+    // Labels are current states; examples are the states that follow them.
+    transitions: BayesianCounter<S, S>,
+    previous: Option<S>,
+}
+
+impl<S: Countable> TransitionCounter<S> {
+    pub fn new() -> Self {
+        Self {
+            transitions: BayesianCounter::new(),
+            previous: None,
+        }
+    }
+
+    /// Observes the next state of the current sequence, counting a transition from the
+    /// previously observed state.
+    pub fn observe(&mut self, state: S) {
+        if let Some(previous) = self.previous.replace(state.clone()) {
+            self.transitions.observe(state, previous);
+        }
+    }
+
+    /// Ends the current sequence, so that the next observed state begins a new one.
+    pub fn end_sequence(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn observe_transition(&mut self, current: S, next: S) {
+        self.transitions.observe(next, current);
+    }
+
+    pub fn transition_count(&self, current: S, next: S) -> u64 {
+        self.transitions.count_of(&next, &current)
+    }
+
+    pub fn total_transitions(&self) -> u64 {
+        self.transitions.total()
+    }
+
+    pub fn p_next_given_current(&self, next: S, current: S) -> CountingRatio {
+        CountingRatio::ratio(
+            self.transitions.count_of(&next, &current),
+            self.transitions.label_count_of(&current),
+        )
+    }
+
+    /// Every state appearing in a transition, in ascending order.
+    pub fn states(&self) -> BTreeSet<S> {
+        let mut states = self.transitions.examples();
+        states.extend(self.transitions.labels());
+        states
+    }
+
+    /// Estimates the long-run fraction of time spent in each state under the observed
+    /// transition probabilities. States without observed outgoing transitions are treated
+    /// as remaining where they are.
+    #[cfg(feature = "std")]
+    pub fn stationary_distribution(&self) -> Vec<(S, f64)> {
+        let states: Vec<S> = self.states().into_iter().collect();
+        if states.is_empty() {
+            return Vec::new();
+        }
+        let mut distribution = vec![1.0 / states.len() as f64; states.len()];
+        for _ in 0..100_000 {
+            // Half of the mass stays put, which makes the chain aperiodic without
+            // changing its stationary distribution.
+            let mut next: Vec<f64> = distribution.iter().map(|p| p / 2.0).collect();
+            for (i, current) in states.iter().enumerate() {
+                let outgoing = self.transitions.label_count_of(current);
+                if outgoing == 0 {
+                    next[i] += distribution[i] / 2.0;
+                } else {
+                    for (j, following) in states.iter().enumerate() {
+                        let p =
+                            self.transitions.count_of(following, current) as f64 / outgoing as f64;
+                        next[j] += distribution[i] * p / 2.0;
+                    }
+                }
+            }
+            let change: f64 = next
+                .iter()
+                .zip(distribution.iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum();
+            distribution = next;
+            if change < 1e-15 {
+                break;
+            }
+        }
+        states.into_iter().zip(distribution).collect()
+    }
+}
+
+impl<S: Countable> Default for TransitionCounter<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,195 @@
+//! Renders ratios as Markdown or LaTeX, for pasting counts into wikis and papers. A single
+//! ratio becomes one table row of its matches, observations, and percentage, while a
+//! `GroupedRatio` or the label frequencies of a `BayesianCounter` become a complete table
+//! whose columns are padded to line up.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, GroupedRatio};
+//!
+//! let ratio = CountingRatio::ratio(15, 100);
+//! assert_eq!(ratio.to_markdown_row(), "| 15 | 100 | 15.00% |");
+//! assert_eq!(ratio.to_latex(), r"15 & 100 & 15.00\% \\");
+//!
+//! let mut suites = GroupedRatio::new();
+//! suites.add("unit", CountingRatio::ratio(95, 100));
+//! suites.add("end_to_end", CountingRatio::ratio(8, 10));
+//! assert_eq!(
+//!     suites.to_markdown("Suite"),
+//!     "\
+//! | Suite      | Matches | Observations | Percent |
+//! |:-----------|--------:|-------------:|--------:|
+//! | end_to_end |       8 |           10 |  80.00% |
+//! | unit       |      95 |          100 |  95.00% |
+//! "
+//! );
+//! assert_eq!(
+//!     suites.to_latex("Suite"),
+//!     r"\begin{tabular}{lrrr}
+//! \hline
+//! Suite        & Matches & Observations & Percent \\
+//! \hline
+//! end\_to\_end &       8 &           10 & 80.00\% \\
+//! unit         &      95 &          100 & 95.00\% \\
+//! \hline
+//! \end{tabular}
+//! "
+//! );
+//! ```
+
+use crate::{BayesianCounter, Count, Countable, CountingRatio, GenericCountingRatio, GroupedRatio};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+const COLUMNS: [&str; 3] = ["Matches", "Observations", "Percent"];
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// The matches, observations, and percentage as a row of a Markdown table.
+    pub fn to_markdown_row(&self) -> String {
+        let [matches, observations, percent] = self.cells();
+        format!("| {matches} | {observations} | {percent} |")
+    }
+
+    /// The matches, observations, and percentage as a row of a LaTeX `tabular`.
+    pub fn to_latex(&self) -> String {
+        let [matches, observations, percent] = self.cells();
+        format!(
+            "{matches} & {observations} & {} \\\\",
+            escape_latex(&percent)
+        )
+    }
+
+    fn cells(&self) -> [String; 3] {
+        [
+            self.matches().to_string(),
+            self.observations().to_string(),
+            format!("{self:#}"),
+        ]
+    }
+}
+
+impl<K: Ord + Display> GroupedRatio<K> {
+    /// A Markdown table of every key and its ratio, in ascending key order. `heading` names
+    /// the column of keys.
+    pub fn to_markdown(&self, heading: &str) -> String {
+        markdown_table(heading, self.iter())
+    }
+
+    /// A LaTeX `tabular` of every key and its ratio, in ascending key order. `heading` names
+    /// the column of keys.
+    pub fn to_latex(&self, heading: &str) -> String {
+        latex_table(heading, self.iter())
+    }
+}
+
+impl<L: Countable + Display, S: Countable> BayesianCounter<L, S> {
+    /// A Markdown table of `p_label()` for every label. `heading` names the column of labels.
+    pub fn to_markdown(&self, heading: &str) -> String {
+        markdown_table(heading, self.label_rows())
+    }
+
+    /// A LaTeX `tabular` of `p_label()` for every label. `heading` names the column of labels.
+    pub fn to_latex(&self, heading: &str) -> String {
+        latex_table(heading, self.label_rows())
+    }
+
+    fn label_rows(&self) -> impl Iterator<Item = (L, CountingRatio)> + '_ {
+        self.labels()
+            .map(|label| (label.clone(), self.p_label(label)))
+    }
+}
+
+// The cells of each row, with the name of the row first, and the widest cell of each column.
+fn table<N: Display>(
+    heading: String,
+    rows: impl Iterator<Item = (N, CountingRatio)>,
+    escape: fn(&str) -> String,
+) -> (Vec<[String; 4]>, [usize; 4]) {
+    let mut cells = alloc::vec![[
+        heading,
+        COLUMNS[0].into(),
+        COLUMNS[1].into(),
+        COLUMNS[2].into()
+    ]];
+    for (name, ratio) in rows {
+        let [matches, observations, percent] = ratio.cells();
+        cells.push([
+            escape(&name.to_string()),
+            matches,
+            observations,
+            escape(&percent),
+        ]);
+    }
+    let mut widths = [0; 4];
+    for row in cells.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    (cells, widths)
+}
+
+fn markdown_table<N: Display>(
+    heading: &str,
+    rows: impl Iterator<Item = (N, CountingRatio)>,
+) -> String {
+    let (cells, [w0, w1, w2, w3]) = table(escape_markdown(heading), rows, escape_markdown);
+    let mut result = String::new();
+    for (index, [name, matches, observations, percent]) in cells.iter().enumerate() {
+        if index == 0 {
+            result +=
+                &format!("| {name:w0$} | {matches:w1$} | {observations:w2$} | {percent:w3$} |\n");
+            result += &format!(
+                "|:{:-<w0$}-|-{:->w1$}:|-{:->w2$}:|-{:->w3$}:|\n",
+                "", "", "", ""
+            );
+        } else {
+            result += &format!(
+                "| {name:w0$} | {matches:>w1$} | {observations:>w2$} | {percent:>w3$} |\n"
+            );
+        }
+    }
+    result
+}
+
+fn latex_table<N: Display>(
+    heading: &str,
+    rows: impl Iterator<Item = (N, CountingRatio)>,
+) -> String {
+    let (cells, [w0, w1, w2, w3]) = table(escape_latex(heading), rows, escape_latex);
+    let mut result = String::from("\\begin{tabular}{lrrr}\n\\hline\n");
+    for (index, [name, matches, observations, percent]) in cells.iter().enumerate() {
+        if index == 0 {
+            result += &format!(
+                "{name:w0$} & {matches:w1$} & {observations:w2$} & {percent:w3$} \\\\\n\\hline\n"
+            );
+        } else {
+            result += &format!(
+                "{name:w0$} & {matches:>w1$} & {observations:>w2$} & {percent:>w3$} \\\\\n"
+            );
+        }
+    }
+    result + "\\hline\n\\end{tabular}\n"
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn escape_latex(text: &str) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '~' => result += "\\textasciitilde{}",
+            '^' => result += "\\textasciicircum{}",
+            '\\' => result += "\\textbackslash{}",
+            _ => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,175 @@
+//! A `RatioMatrix` keeps a `CountingRatio` for every combination of a row key and a column
+//! key, such as the success rate of each browser on each operating system, along with the
+//! marginal ratio of each row and each column. Its `Display` form is a table with a row and
+//! a column of totals, in which combinations never observed show as `-`. The formatter's
+//! precision sets the decimal places of the percentages.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, RatioMatrix};
+//!
+//! let mut sessions = RatioMatrix::new();
+//! for (browser, os, succeeded) in [
+//!     ("chrome", "linux", true),
+//!     ("chrome", "linux", true),
+//!     ("chrome", "macos", false),
+//!     ("firefox", "linux", true),
+//!     ("firefox", "linux", false),
+//! ] {
+//!     sessions.observe(browser, os, succeeded);
+//! }
+//!
+//! assert_eq!(sessions.get(&"chrome", &"linux"), CountingRatio::ratio(2, 2));
+//! assert_eq!(sessions.row(&"chrome"), CountingRatio::ratio(2, 3));
+//! assert_eq!(sessions.column(&"linux"), CountingRatio::ratio(3, 4));
+//! assert_eq!(sessions.total(), CountingRatio::ratio(3, 5));
+//! let table = format!("{sessions:.0}");
+//! assert_eq!(
+//!     table.lines().collect::<Vec<_>>(),
+//!     [
+//!         "              linux     macos      Total",
+//!         "chrome   2/2 (100%)  0/1 (0%)  2/3 (67%)",
+//!         "firefox   1/2 (50%)         -  1/2 (50%)",
+//!         "Total     3/4 (75%)  0/1 (0%)  3/5 (60%)",
+//!     ]
+//! );
+//! ```
+
+use crate::CountingRatio;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatioMatrix<R: Ord, C: Ord> {
+    rows: BTreeMap<R, BTreeMap<C, CountingRatio>>,
+}
+
+impl<R: Ord, C: Ord> RatioMatrix<R, C> {
+    pub fn new() -> Self {
+        Self {
+            rows: BTreeMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, row: R, column: C, condition_met: bool) {
+        self.rows
+            .entry(row)
+            .or_default()
+            .entry(column)
+            .or_default()
+            .observe(condition_met);
+    }
+
+    pub fn add(&mut self, row: R, column: C, counts: CountingRatio) {
+        *self.rows.entry(row).or_default().entry(column).or_default() += counts;
+    }
+
+    /// The ratio for the combination of `row` and `column`, which is undefined if it was
+    /// never observed.
+    pub fn get(&self, row: &R, column: &C) -> CountingRatio {
+        self.rows
+            .get(row)
+            .and_then(|columns| columns.get(column))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The ratio across every column of `row`.
+    pub fn row(&self, row: &R) -> CountingRatio {
+        self.rows
+            .get(row)
+            .map_or_else(CountingRatio::new, |columns| columns.values().sum())
+    }
+
+    /// The ratio across every row of `column`.
+    pub fn column(&self, column: &C) -> CountingRatio {
+        self.rows
+            .values()
+            .filter_map(|columns| columns.get(column))
+            .sum()
+    }
+
+    /// The ratio across every combination.
+    pub fn total(&self) -> CountingRatio {
+        self.rows
+            .values()
+            .flat_map(|columns| columns.values())
+            .sum()
+    }
+
+    /// Every row key, in ascending order.
+    pub fn row_keys(&self) -> impl Iterator<Item = &R> {
+        self.rows.keys()
+    }
+
+    /// Every column key observed in any row, in ascending order.
+    pub fn column_keys(&self) -> BTreeSet<&C> {
+        self.rows
+            .values()
+            .flat_map(|columns| columns.keys())
+            .collect()
+    }
+
+    /// Iterates over every observed combination and its ratio, in ascending order of row
+    /// and then column.
+    pub fn iter(&self) -> impl Iterator<Item = (&R, &C, CountingRatio)> {
+        self.rows.iter().flat_map(|(row, columns)| {
+            columns
+                .iter()
+                .map(move |(column, ratio)| (row, column, *ratio))
+        })
+    }
+}
+
+impl<R: Ord, C: Ord> Default for RatioMatrix<R, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Ord + Display, C: Ord + Display> Display for RatioMatrix<R, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let cell = |ratio: CountingRatio| {
+            if ratio.defined() {
+                format!("{ratio:.precision$}")
+            } else {
+                String::from("-")
+            }
+        };
+        let columns = self.column_keys();
+        let mut lines: Vec<Vec<String>> = Vec::new();
+        let mut heading = alloc::vec![String::new()];
+        heading.extend(columns.iter().map(|column| column.to_string()));
+        heading.push(String::from("Total"));
+        lines.push(heading);
+        for row in self.row_keys() {
+            let mut line = alloc::vec![row.to_string()];
+            line.extend(columns.iter().map(|column| cell(self.get(row, column))));
+            line.push(cell(self.row(row)));
+            lines.push(line);
+        }
+        let mut totals = alloc::vec![String::from("Total")];
+        totals.extend(columns.iter().map(|column| cell(self.column(column))));
+        totals.push(cell(self.total()));
+        lines.push(totals);
+
+        let mut widths = alloc::vec![0; columns.len() + 2];
+        for line in lines.iter() {
+            for (width, text) in widths.iter_mut().zip(line) {
+                *width = (*width).max(text.chars().count());
+            }
+        }
+        for line in lines.iter() {
+            write!(f, "{:width$}", line[0], width = widths[0])?;
+            for (text, width) in line.iter().zip(widths.iter()).skip(1) {
+                write!(f, "  {text:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
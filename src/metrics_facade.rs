@@ -0,0 +1,55 @@
+//! Emitting a `CountingRatio` through the `metrics` facade, so that whichever recorder the
+//! application installed picks it up. Requires the `metrics` feature.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//! use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+//! use std::collections::HashMap;
+//!
+//! let recorder = DebuggingRecorder::new();
+//! let snapshotter = recorder.snapshotter();
+//! metrics::with_local_recorder(&recorder, || {
+//!     CountingRatio::ratio(87, 100).describe_and_emit("cache_hits");
+//!     // An undefined ratio leaves the gauge at its last value.
+//!     CountingRatio::new().describe_and_emit("cache_hits");
+//!     CountingRatio::new().describe_and_emit("cold_cache_hits");
+//! });
+//!
+//! let values: HashMap<String, DebugValue> = snapshotter
+//!     .snapshot()
+//!     .into_vec()
+//!     .into_iter()
+//!     .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+//!     .collect();
+//! assert_eq!(values["cache_hits_matches_total"], DebugValue::Counter(87));
+//! assert_eq!(values["cache_hits_observations_total"], DebugValue::Counter(100));
+//! assert_eq!(values["cache_hits_ratio"], DebugValue::Gauge(0.87.into()));
+//! assert_eq!(values["cold_cache_hits_observations_total"], DebugValue::Counter(0));
+//! assert!(!values.contains_key("cold_cache_hits_ratio"));
+//! ```
+
+use crate::CountingRatio;
+use metrics::{counter, describe_counter, describe_gauge, gauge};
+
+impl CountingRatio {
+    /// Describes and emits the counts as the absolute counters `<name>_matches_total` and
+    /// `<name>_observations_total`, and the ratio as the gauge `<name>_ratio`. The gauge is
+    /// left unchanged while the ratio is undefined. Call it whenever the counts change or
+    /// on a regular schedule.
+    pub fn describe_and_emit(&self, name: &str) {
+        let matches = format!("{name}_matches_total");
+        let observations = format!("{name}_observations_total");
+        let ratio = format!("{name}_ratio");
+        describe_counter!(matches.clone(), "Observations meeting the condition");
+        describe_counter!(observations.clone(), "All observations");
+        describe_gauge!(
+            ratio.clone(),
+            "Fraction of observations meeting the condition"
+        );
+        counter!(matches).absolute(self.matches());
+        counter!(observations).absolute(self.observations());
+        if let Some(value) = self.value() {
+            gauge!(ratio).set(value);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! A `NaiveBayesClassifier` extends `BayesianCounter` to observations consisting of several
+//! features. Under the naive assumption that features are conditionally independent given
+//! the label, each feature position is tracked by its own `BayesianCounter`.
+//!
+//! ```
+//! use counting_ratio::NaiveBayesClassifier;
+//!
+//! let mut classifier = NaiveBayesClassifier::with_smoothing(1);
+//! classifier.train(&["sunny", "hot"], "stay");
+//! classifier.train(&["sunny", "mild"], "go");
+//! classifier.train(&["rainy", "mild"], "stay");
+//! classifier.train(&["sunny", "mild"], "go");
+//!
+//! assert_eq!(classifier.classify(&["sunny", "mild"]), Some("go"));
+//! assert_eq!(classifier.classify(&["rainy", "hot"]), Some("stay"));
+//! ```
+
+use crate::{BayesianCounter, Countable};
+use histogram_macros::*;
+use std::collections::BTreeMap;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NaiveBayesClassifier<L: Countable, F: Countable> {
+    features: Vec<BayesianCounter<L, F>>,
+    labels: BTreeMap<L, u64>,
+    total: u64,
+    smoothing: u64,
+}
+
+impl<L: Countable, F: Countable> NaiveBayesClassifier<L, F> {
+    pub fn new() -> Self {
+        Self::with_smoothing(0)
+    }
+
+    /// Applies additive smoothing of `alpha` to every feature; see
+    /// `BayesianCounter::with_smoothing()`.
+    pub fn with_smoothing(alpha: u64) -> Self {
+        Self {
+            features: Vec::new(),
+            labels: BTreeMap::new(),
+            total: 0,
+            smoothing: alpha,
+        }
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.features.len()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Panics if `features` differs in length from previously trained observations.
+    pub fn train(&mut self, features: &[F], label: L) {
+        if self.total == 0 {
+            self.features = (0..features.len())
+                .map(|_| BayesianCounter::with_smoothing(self.smoothing))
+                .collect();
+        }
+        assert_eq!(
+            features.len(),
+            self.features.len(),
+            "All observations must have the same number of features"
+        );
+        for (counter, feature) in self.features.iter_mut().zip(features.iter()) {
+            counter.observe(feature.clone(), label.clone());
+        }
+        bump!(self.labels, label);
+        self.total += 1;
+    }
+
+    /// Natural logarithm of `p(label) * p(f_1 | label) * ... * p(f_n | label)`, which is
+    /// proportional to `p(label | features)`.
+    pub fn log_score(&self, features: &[F], label: L) -> f64 {
+        self.log_score_of(features, &label)
+    }
+
+    fn log_score_of(&self, features: &[F], label: &L) -> f64 {
+        let label_count = self.labels.get(label).copied().unwrap_or(0) as f64;
+        let prior = (label_count / self.total as f64).ln();
+        self.features
+            .iter()
+            .zip(features.iter())
+            .map(|(counter, feature)| {
+                f64::from(counter.p_example_given_label_of(feature, label)).ln()
+            })
+            .fold(prior, |sum, p| sum + p)
+    }
+
+    /// Returns `p(label | features)` for every label, normalized to sum to one.
+    /// Labels appear in ascending order.
+    pub fn posterior(&self, features: &[F]) -> Vec<(L, f64)> {
+        let scores: Vec<(L, f64)> = self
+            .labels
+            .keys()
+            .map(|label| (label.clone(), self.log_score_of(features, label)))
+            .collect();
+        let max = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::NEG_INFINITY {
+            return scores.into_iter().map(|(label, _)| (label, 0.0)).collect();
+        }
+        let normalizer: f64 = scores.iter().map(|(_, score)| (score - max).exp()).sum();
+        scores
+            .into_iter()
+            .map(|(label, score)| (label, (score - max).exp() / normalizer))
+            .collect()
+    }
+
+    /// Returns the most probable label, or `None` if nothing has been trained. Ties are
+    /// broken in favor of the smallest label.
+    pub fn classify(&self, features: &[F]) -> Option<L> {
+        let mut best: Option<(&L, f64)> = None;
+        for label in self.labels.keys() {
+            let score = self.log_score_of(features, label);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((label, score));
+            }
+        }
+        best.map(|(label, _)| label.clone())
+    }
+}
+
+impl<L: Countable, F: Countable> Default for NaiveBayesClassifier<L, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,136 @@
+//! A `NestedRatio` counts observations under multi-level keys, such as region, then store,
+//! then product. Every observation is rolled up into each level of its path, so that any
+//! node holds the ratio across everything beneath it, and the hierarchy can be drilled into
+//! one level at a time.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, NestedRatio};
+//!
+//! let mut in_stock = NestedRatio::new();
+//! in_stock.observe(["north", "oslo", "apples"], true);
+//! in_stock.observe(["north", "oslo", "pears"], false);
+//! in_stock.observe(["north", "bergen", "apples"], true);
+//! in_stock.observe(["south", "rome", "apples"], false);
+//!
+//! assert_eq!(in_stock.total(), CountingRatio::ratio(2, 4));
+//! assert_eq!(in_stock.get(&["north"]), CountingRatio::ratio(2, 3));
+//! assert_eq!(in_stock.get(&["north", "oslo"]), CountingRatio::ratio(1, 2));
+//! assert_eq!(in_stock.get(&["east"]), CountingRatio::new());
+//!
+//! let north = in_stock.node(&["north"]).unwrap();
+//! let stores: Vec<_> = north.children().map(|(store, node)| (*store, node.total())).collect();
+//! assert_eq!(
+//!     stores,
+//!     vec![("bergen", CountingRatio::ratio(1, 1)), ("oslo", CountingRatio::ratio(1, 2))]
+//! );
+//!
+//! assert_eq!(
+//!     format!("{}", in_stock.node(&["north"]).unwrap()),
+//!     "\
+//! bergen: 1/1 (100.00%)
+//!   apples: 1/1 (100.00%)
+//! oslo: 1/2 (50.00%)
+//!   apples: 1/1 (100.00%)
+//!   pears: 0/1 (0.00%)
+//! "
+//! );
+//! ```
+
+use crate::CountingRatio;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NestedRatio<K: Ord> {
+    total: CountingRatio,
+    children: BTreeMap<K, NestedRatio<K>>,
+}
+
+impl<K: Ord> NestedRatio<K> {
+    pub fn new() -> Self {
+        Self {
+            total: CountingRatio::new(),
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Records an observation under `path`, rolling it up into every level of the path.
+    pub fn observe<P: IntoIterator<Item = K>>(&mut self, path: P, condition_met: bool) {
+        let matches = if condition_met { 1 } else { 0 };
+        self.add(path, CountingRatio::ratio(matches, 1));
+    }
+
+    /// Adds `counts` under `path`, rolling them up into every level of the path.
+    pub fn add<P: IntoIterator<Item = K>>(&mut self, path: P, counts: CountingRatio) {
+        let mut node = self;
+        node.total += counts;
+        for key in path {
+            node = node.children.entry(key).or_default();
+            node.total += counts;
+        }
+    }
+
+    /// The ratio across everything observed beneath this node.
+    pub fn total(&self) -> CountingRatio {
+        self.total
+    }
+
+    /// The rolled-up ratio at `path`, which is undefined if nothing was observed there.
+    pub fn get(&self, path: &[K]) -> CountingRatio {
+        self.node(path)
+            .map_or_else(CountingRatio::new, |node| node.total)
+    }
+
+    /// The node at `path`, for drilling further down.
+    pub fn node(&self, path: &[K]) -> Option<&Self> {
+        path.iter()
+            .try_fold(self, |node, key| node.children.get(key))
+    }
+
+    /// The nodes one level down, in ascending key order.
+    pub fn children(&self) -> impl Iterator<Item = (&K, &Self)> {
+        self.children.iter()
+    }
+
+    /// Every node beneath this one with its path and ratio, each parent before its
+    /// children, in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<&K>, CountingRatio)> {
+        let mut result = Vec::new();
+        self.collect_into(&mut Vec::new(), &mut result);
+        result.into_iter()
+    }
+
+    fn collect_into<'a>(
+        &'a self,
+        path: &mut Vec<&'a K>,
+        result: &mut Vec<(Vec<&'a K>, CountingRatio)>,
+    ) {
+        for (key, child) in self.children.iter() {
+            path.push(key);
+            result.push((path.clone(), child.total));
+            child.collect_into(path, result);
+            path.pop();
+        }
+    }
+}
+
+impl<K: Ord> Default for NestedRatio<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shows every node beneath this one as `key: ratio`, indenting each level by two spaces.
+impl<K: Ord + Display> Display for NestedRatio<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (path, ratio) in self.iter() {
+            let indent = 2 * (path.len() - 1);
+            if let Some(key) = path.last() {
+                writeln!(f, "{:indent$}{key}: {ratio}", "")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! An `NGramCounter` counts every run of `order` consecutive tokens in its training
+//! sequences. Each token's probability is conditioned on the `order - 1` tokens before it,
+//! making for a tiny language model.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, NGramCounter};
+//!
+//! let mut bigrams = NGramCounter::new(2);
+//! bigrams.observe_sequence(&"the cat sat on the mat".split(' ').collect::<Vec<_>>());
+//!
+//! assert_eq!(bigrams.count(&["the", "cat"]), 1);
+//! assert_eq!(bigrams.p_next_given_context("cat", &["the"]), CountingRatio::ratio(1, 2));
+//! assert!((bigrams.perplexity(&["the", "cat", "sat"]).unwrap() - 2.0f64.sqrt()).abs() < 1e-12);
+//! assert_eq!(bigrams.perplexity(&["the", "dog"]), None);
+//! ```
+
+use crate::{Countable, CountingRatio};
+use histogram_macros::*;
+use std::collections::BTreeMap;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NGramCounter<T: Countable> {
+    order: usize,
+    ngrams: BTreeMap<Vec<T>, u64>,
+    contexts: BTreeMap<Vec<T>, u64>,
+}
+
+impl<T: Countable> NGramCounter<T> {
+    /// `order` is the length of the counted n-grams, which must be at least one.
+    pub fn new(order: usize) -> Self {
+        assert!(order > 0, "n-gram order must be positive");
+        Self {
+            order,
+            ngrams: BTreeMap::new(),
+            contexts: BTreeMap::new(),
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Counts every n-gram within `sequence`. Sequences shorter than the order contribute
+    /// nothing.
+    pub fn observe_sequence(&mut self, sequence: &[T]) {
+        for ngram in sequence.windows(self.order) {
+            let ngram = ngram.to_vec();
+            let context = ngram[..self.order - 1].to_vec();
+            bump!(self.ngrams, ngram);
+            bump!(self.contexts, context);
+        }
+    }
+
+    /// The number of times `ngram` was observed. Returns 0 if `ngram` is not of the
+    /// counter's order.
+    pub fn count(&self, ngram: &[T]) -> u64 {
+        self.ngrams.get(ngram).copied().unwrap_or(0)
+    }
+
+    /// The probability of `next` following the `order - 1` tokens of `context`.
+    pub fn p_next_given_context(&self, next: T, context: &[T]) -> CountingRatio {
+        let mut ngram = context.to_vec();
+        ngram.push(next);
+        CountingRatio::ratio(
+            self.count(&ngram),
+            self.contexts.get(context).copied().unwrap_or(0),
+        )
+    }
+
+    /// The perplexity of `sequence` under the model: the inverse geometric mean of the
+    /// probabilities of each of its n-grams. Returns `None` if `sequence` contains no
+    /// n-grams or any of its n-grams has zero probability.
+    pub fn perplexity(&self, sequence: &[T]) -> Option<f64> {
+        let mut log_sum = 0.0;
+        let mut ngram_count = 0;
+        for ngram in sequence.windows(self.order) {
+            let (context, next) = ngram.split_at(self.order - 1);
+            let p = self
+                .p_next_given_context(next[0].clone(), context)
+                .value()?;
+            if p == 0.0 {
+                return None;
+            }
+            log_sum += p.ln();
+            ngram_count += 1;
+        }
+        if ngram_count == 0 {
+            None
+        } else {
+            Some((-log_sum / ngram_count as f64).exp())
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! An OpenTelemetry bridge that reports a ratio as two observable counters,
+//! `<name>.matches` and `<name>.observations`, plus an observable gauge `<name>.ratio`. The
+//! instruments read the ratio whenever the meter provider collects, so the application
+//! only has to keep counting. Requires the `opentelemetry` feature.
+//!
+//! ```
+//! use counting_ratio::AtomicCountingRatio;
+//! use counting_ratio::otel::register_atomic_ratio;
+//! use opentelemetry::metrics::MeterProvider;
+//! use opentelemetry::KeyValue;
+//! use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+//! use opentelemetry_sdk::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+//! use std::sync::Arc;
+//!
+//! let exporter = InMemoryMetricExporter::default();
+//! let provider = SdkMeterProvider::builder()
+//!     .with_periodic_exporter(exporter.clone())
+//!     .build();
+//! let tier = KeyValue::new("tier", "memory");
+//!
+//! let cache_hits = Arc::new(AtomicCountingRatio::new());
+//! let _instruments = register_atomic_ratio(
+//!     &provider.meter("cache"),
+//!     "cache.hits",
+//!     &[tier.clone()],
+//!     cache_hits.clone(),
+//! );
+//! for hit in [true, true, false, true] {
+//!     cache_hits.observe(hit);
+//! }
+//! provider.force_flush().unwrap();
+//!
+//! // Every data point as its instrument name, value, and attributes.
+//! let mut points = Vec::new();
+//! let metrics = exporter.get_finished_metrics().unwrap();
+//! for metric in metrics.iter().flat_map(|m| m.scope_metrics()).flat_map(|s| s.metrics()) {
+//!     let name = metric.name().to_string();
+//!     match metric.data() {
+//!         AggregatedMetrics::U64(MetricData::Sum(sum)) => points.extend(
+//!             sum.data_points()
+//!                 .map(|p| (name.clone(), p.value() as f64, p.attributes().cloned().collect())),
+//!         ),
+//!         AggregatedMetrics::F64(MetricData::Gauge(gauge)) => points.extend(
+//!             gauge.data_points()
+//!                 .map(|p| (name.clone(), p.value(), p.attributes().cloned().collect())),
+//!         ),
+//!         data => panic!("unexpected {data:?}"),
+//!     }
+//! }
+//! points.sort_by(|a, b| a.0.cmp(&b.0));
+//! assert_eq!(
+//!     points,
+//!     [
+//!         ("cache.hits.matches".to_string(), 3.0, vec![tier.clone()]),
+//!         ("cache.hits.observations".to_string(), 4.0, vec![tier.clone()]),
+//!         ("cache.hits.ratio".to_string(), 0.75, vec![tier]),
+//!     ]
+//! );
+//! ```
+
+use crate::{AtomicCountingRatio, CountingRatio};
+use opentelemetry::metrics::{Meter, ObservableCounter, ObservableGauge};
+use opentelemetry::KeyValue;
+use std::sync::Arc;
+
+/// The instruments created by `register_ratio()`. Keep it alive for as long as the ratio
+/// should be reported.
+#[derive(Clone, Debug)]
+pub struct OtelRatio {
+    matches: ObservableCounter<u64>,
+    observations: ObservableCounter<u64>,
+    ratio: ObservableGauge<f64>,
+}
+
+impl OtelRatio {
+    pub fn matches(&self) -> &ObservableCounter<u64> {
+        &self.matches
+    }
+
+    pub fn observations(&self) -> &ObservableCounter<u64> {
+        &self.observations
+    }
+
+    pub fn ratio(&self) -> &ObservableGauge<f64> {
+        &self.ratio
+    }
+}
+
+/// Registers instruments with `meter` that report the ratio returned by `source`, tagged
+/// with `attributes`. The gauge reports nothing while the ratio is undefined.
+pub fn register_ratio<F>(meter: &Meter, name: &str, attributes: &[KeyValue], source: F) -> OtelRatio
+where
+    F: Fn() -> CountingRatio + Send + Sync + 'static,
+{
+    let source = Arc::new(source);
+    let attributes: Arc<[KeyValue]> = attributes.into();
+    let (matches_source, matches_attributes) = (source.clone(), attributes.clone());
+    let (observations_source, observations_attributes) = (source.clone(), attributes.clone());
+    OtelRatio {
+        matches: meter
+            .u64_observable_counter(format!("{name}.matches"))
+            .with_description("Observations meeting the condition")
+            .with_callback(move |observer| {
+                observer.observe(matches_source().matches(), &matches_attributes)
+            })
+            .build(),
+        observations: meter
+            .u64_observable_counter(format!("{name}.observations"))
+            .with_description("All observations")
+            .with_callback(move |observer| {
+                observer.observe(
+                    observations_source().observations(),
+                    &observations_attributes,
+                )
+            })
+            .build(),
+        ratio: meter
+            .f64_observable_gauge(format!("{name}.ratio"))
+            .with_description("Fraction of observations meeting the condition")
+            .with_callback(move |observer| {
+                if let Some(value) = source().value() {
+                    observer.observe(value, &attributes);
+                }
+            })
+            .build(),
+    }
+}
+
+/// Registers instruments that report snapshots of `ratio`, which may keep counting from
+/// other threads.
+pub fn register_atomic_ratio(
+    meter: &Meter,
+    name: &str,
+    attributes: &[KeyValue],
+    ratio: Arc<AtomicCountingRatio>,
+) -> OtelRatio {
+    register_ratio(meter, name, attributes, move || ratio.snapshot())
+}
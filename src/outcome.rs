@@ -0,0 +1,137 @@
+//! An `OutcomeCounter` counts observations that each fall into one of several categories,
+//! such as the faces of a die or the status codes of responses. Every outcome's share
+//! comes from the same total, so the shares cannot drift apart as separate boolean counters
+//! could, and together they form the empirical distribution.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, OutcomeCounter};
+//!
+//! let statuses: OutcomeCounter<u16> = [200, 200, 404, 200, 500, 200].into_iter().collect();
+//!
+//! assert_eq!(statuses.total(), 6);
+//! assert_eq!(statuses.count(&200), 4);
+//! assert_eq!(statuses.share(&404), CountingRatio::ratio(1, 6));
+//! assert_eq!(statuses.share(&302), CountingRatio::ratio(0, 6));
+//! assert_eq!(statuses.most_common(), Some((&200, 4)));
+//! assert_eq!(
+//!     statuses.distribution(),
+//!     vec![
+//!         (&200, CountingRatio::ratio(4, 6)),
+//!         (&404, CountingRatio::ratio(1, 6)),
+//!         (&500, CountingRatio::ratio(1, 6)),
+//!     ]
+//! );
+//! ```
+
+use crate::{Countable, CountingRatio};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutcomeCounter<E: Countable> {
+    counts: BTreeMap<E, u64>,
+    total: u64,
+}
+
+impl<E: Countable> OutcomeCounter<E> {
+    pub fn new() -> Self {
+        Self {
+            counts: BTreeMap::new(),
+            total: 0,
+        }
+    }
+
+    pub fn observe(&mut self, outcome: E) {
+        self.add(outcome, 1);
+    }
+
+    /// Records `count` observations of `outcome` at once.
+    pub fn add(&mut self, outcome: E, count: u64) {
+        *self.counts.entry(outcome).or_default() += count;
+        self.total += count;
+    }
+
+    /// Adds every observation counted by `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for (outcome, count) in other.counts.iter() {
+            self.add(outcome.clone(), *count);
+        }
+    }
+
+    pub fn count(&self, outcome: &E) -> u64 {
+        self.counts.get(outcome).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The number of distinct outcomes observed.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The fraction of all observations that were `outcome`, which is undefined before any
+    /// observation.
+    pub fn share(&self, outcome: &E) -> CountingRatio {
+        CountingRatio::ratio(self.count(outcome), self.total)
+    }
+
+    /// Iterates over every observed outcome and its count, in ascending order of outcome.
+    pub fn iter(&self) -> impl Iterator<Item = (&E, u64)> {
+        self.counts.iter().map(|(outcome, count)| (outcome, *count))
+    }
+
+    /// The share of every observed outcome, in ascending order of outcome.
+    pub fn distribution(&self) -> Vec<(&E, CountingRatio)> {
+        self.iter()
+            .map(|(outcome, count)| (outcome, CountingRatio::ratio(count, self.total)))
+            .collect()
+    }
+
+    /// The outcome observed most often, with its count. Ties go to the smallest outcome.
+    pub fn most_common(&self) -> Option<(&E, u64)> {
+        self.iter().fold(None, |best, (outcome, count)| match best {
+            Some((_, most)) if most >= count => best,
+            _ => Some((outcome, count)),
+        })
+    }
+}
+
+impl<E: Countable> Default for OutcomeCounter<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Countable> FromIterator<E> for OutcomeCounter<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<E: Countable> Extend<E> for OutcomeCounter<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        for outcome in iter {
+            self.observe(outcome);
+        }
+    }
+}
+
+/// Shows the share of each observed outcome on its own line.
+impl<E: Countable + Display> Display for OutcomeCounter<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (outcome, share) in self.distribution() {
+            writeln!(f, "{outcome}: {share}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! A `PChart` is the classic control chart for the proportion of nonconforming items in
+//! batches of varying size. The center line is the overall proportion across every batch,
+//! unless a known standard is given, and each batch gets its own 3-sigma limits since
+//! smaller batches vary more.
+//!
+//! ```
+//! use counting_ratio::{ControlSignal, CountingRatio, PChart};
+//!
+//! let chart: PChart = [
+//!     CountingRatio::ratio(4, 100),
+//!     CountingRatio::ratio(6, 120),
+//!     CountingRatio::ratio(3, 80),
+//!     CountingRatio::ratio(25, 100),
+//!     CountingRatio::ratio(5, 100),
+//! ]
+//! .into_iter()
+//! .collect();
+//!
+//! assert_eq!(chart.center_line(), Some(43.0 / 500.0));
+//! let (lower, upper) = chart.limits(2).unwrap();
+//! assert!(lower == 0.0 && upper > chart.limits(1).unwrap().1);
+//! assert_eq!(chart.violations(), vec![(3, ControlSignal::AboveUpperLimit)]);
+//!
+//! let known = PChart::new().with_standard(0.2);
+//! assert_eq!(known.center_line(), Some(0.2));
+//! ```
+
+use crate::{ControlSignal, CountingRatio};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PChart {
+    batches: Vec<CountingRatio>,
+    standard: Option<f64>,
+}
+
+impl PChart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Centers the chart on a known `standard` proportion, which must lie within
+    /// `[0.0, 1.0]`, rather than on the proportion across the batches.
+    pub fn with_standard(self, standard: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&standard),
+            "PChart standard must lie within [0.0, 1.0]"
+        );
+        Self {
+            standard: Some(standard),
+            ..self
+        }
+    }
+
+    /// Adds the nonconforming items among one batch, returning its index.
+    pub fn add_batch(&mut self, batch: CountingRatio) -> usize {
+        self.batches.push(batch);
+        self.batches.len() - 1
+    }
+
+    pub fn batches(&self) -> &[CountingRatio] {
+        &self.batches
+    }
+
+    pub fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
+    /// The known standard, if given, or else the proportion across every batch. Returns
+    /// `None` if there is no standard and no batch has any observations.
+    pub fn center_line(&self) -> Option<f64> {
+        self.standard
+            .or_else(|| self.batches.iter().sum::<CountingRatio>().value())
+    }
+
+    /// The lower and upper 3-sigma control limits for the batch at `index`, clamped to
+    /// `[0.0, 1.0]`. Returns `None` if there is no such batch, it has no observations, or
+    /// the chart has no center line.
+    pub fn limits(&self, index: usize) -> Option<(f64, f64)> {
+        self.limits_around(self.center_line()?, index)
+    }
+
+    // The limits for the batch at `index` around `center`, which callers examining many
+    // batches compute only once.
+    fn limits_around(&self, center: f64, index: usize) -> Option<(f64, f64)> {
+        let size = self.batches.get(index)?.observations();
+        if size == 0 {
+            return None;
+        }
+        let margin = 3.0 * (center * (1.0 - center) / size as f64).sqrt();
+        Some(((center - margin).max(0.0), (center + margin).min(1.0)))
+    }
+
+    /// Whether the batch at `index` lies outside its control limits.
+    pub fn signal(&self, index: usize) -> Option<ControlSignal> {
+        self.signal_around(self.center_line()?, index)
+    }
+
+    fn signal_around(&self, center: f64, index: usize) -> Option<ControlSignal> {
+        let (lower, upper) = self.limits_around(center, index)?;
+        let proportion = self.batches[index].value()?;
+        if proportion > upper {
+            Some(ControlSignal::AboveUpperLimit)
+        } else if proportion < lower {
+            Some(ControlSignal::BelowLowerLimit)
+        } else {
+            None
+        }
+    }
+
+    /// Every batch outside its control limits, with its index, in batch order.
+    pub fn violations(&self) -> Vec<(usize, ControlSignal)> {
+        let Some(center) = self.center_line() else {
+            return Vec::new();
+        };
+        (0..self.batches.len())
+            .filter_map(|index| Some((index, self.signal_around(center, index)?)))
+            .collect()
+    }
+}
+
+impl FromIterator<CountingRatio> for PChart {
+    fn from_iter<I: IntoIterator<Item = CountingRatio>>(iter: I) -> Self {
+        Self {
+            batches: iter.into_iter().collect(),
+            standard: None,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Parallel counting with `rayon`. Each worker thread counts its share of the observations
+//! separately, and the partial counts are then added together. Requires the `rayon`
+//! feature. Because ratios implement `Sum`, a parallel iterator of per-shard ratios can also
+//! be pooled with `sum()`.
+//!
+//! ```
+//! use counting_ratio::{BayesianCounter, CountingRatio};
+//! use rayon::prelude::*;
+//!
+//! let evens: CountingRatio = (0..100_000_u64).into_par_iter().map(|n| n % 2 == 0).collect();
+//! assert_eq!(evens, CountingRatio::ratio(50_000, 100_000));
+//!
+//! let mut more = evens;
+//! more.par_extend((0..10_u64).into_par_iter().map(|n| n < 5));
+//! assert_eq!(more, CountingRatio::ratio(50_005, 100_010));
+//!
+//! let shards = vec![CountingRatio::ratio(1, 4); 8];
+//! assert_eq!(shards.par_iter().sum::<CountingRatio>(), CountingRatio::ratio(8, 32));
+//!
+//! let parity: BayesianCounter<&str, u64> = (0..1000_u64)
+//!     .into_par_iter()
+//!     .map(|n| (n % 10, if n % 2 == 0 { "even" } else { "odd" }))
+//!     .collect();
+//! assert_eq!(parity.label_count("even"), 500);
+//! assert_eq!(parity.count(3, "odd"), 100);
+//! ```
+
+use crate::{BayesianCounter, Count, Countable, GenericCountingRatio};
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+impl<T: Count + Send> FromParallelIterator<bool> for GenericCountingRatio<T> {
+    fn from_par_iter<I: IntoParallelIterator<Item = bool>>(par_iter: I) -> Self {
+        par_iter
+            .into_par_iter()
+            .fold(Self::new, |mut ratio, condition_met| {
+                ratio.observe(condition_met);
+                ratio
+            })
+            .reduce(Self::new, |a, b| a + b)
+    }
+}
+
+impl<T: Count + Send> ParallelExtend<bool> for GenericCountingRatio<T> {
+    fn par_extend<I: IntoParallelIterator<Item = bool>>(&mut self, par_iter: I) {
+        *self += Self::from_par_iter(par_iter);
+    }
+}
+
+/// Collects `(example, label)` training pairs, as `observe()` takes them.
+impl<L: Countable + Send, S: Countable + Send> FromParallelIterator<(S, L)>
+    for BayesianCounter<L, S>
+{
+    fn from_par_iter<I: IntoParallelIterator<Item = (S, L)>>(par_iter: I) -> Self {
+        par_iter
+            .into_par_iter()
+            .fold(Self::new, |mut counter, (example, label)| {
+                counter.observe(example, label);
+                counter
+            })
+            .reduce(Self::new, |a, b| a + b)
+    }
+}
+
+impl<L: Countable + Send, S: Countable + Send> ParallelExtend<(S, L)> for BayesianCounter<L, S> {
+    fn par_extend<I: IntoParallelIterator<Item = (S, L)>>(&mut self, par_iter: I) {
+        self.merge(&Self::from_par_iter(par_iter));
+    }
+}
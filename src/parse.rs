@@ -0,0 +1,56 @@
+use crate::{Count, GenericCountingRatio};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseRatioError {
+    MissingSeparator,
+    InvalidMatches,
+    InvalidObservations,
+    InvalidPercentage,
+}
+
+impl Display for ParseRatioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingSeparator => {
+                write!(f, "expected a ratio of the form matches/observations")
+            }
+            Self::InvalidMatches => write!(f, "invalid count of matches"),
+            Self::InvalidObservations => write!(f, "invalid count of observations"),
+            Self::InvalidPercentage => write!(f, "invalid percentage; expected e.g. (15.00%)"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseRatioError {}
+
+/// Parses either the bare form `15/100` or the complete `Display` form `15/100 (15.00%)`.
+/// The percentage is only checked for being well-formed, as it is derived from the counts.
+impl<T: Count> FromStr for GenericCountingRatio<T> {
+    type Err = ParseRatioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (ratio, percentage) = match s.split_once(' ') {
+            Some((ratio, percentage)) => (ratio, Some(percentage.trim())),
+            None => (s, None),
+        };
+        if let Some(percentage) = percentage {
+            percentage
+                .strip_prefix('(')
+                .and_then(|p| p.strip_suffix("%)"))
+                .and_then(|p| p.trim().parse::<f64>().ok())
+                .ok_or(ParseRatioError::InvalidPercentage)?;
+        }
+        let (matches, observations) = ratio
+            .split_once('/')
+            .ok_or(ParseRatioError::MissingSeparator)?;
+        let matches =
+            T::from_str_radix(matches, 10).map_err(|_| ParseRatioError::InvalidMatches)?;
+        let observations = T::from_str_radix(observations, 10)
+            .map_err(|_| ParseRatioError::InvalidObservations)?;
+        Ok(Self::ratio(matches, observations))
+    }
+}
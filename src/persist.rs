@@ -0,0 +1,111 @@
+//! Saving a trained `BayesianCounter` to disk and loading it back, so that it need not be
+//! retrained at startup. Requires the `persist` feature.
+//!
+//! The file holds a JSON object with a `format_version` and the `counter` itself. Files
+//! written by this version of the crate will remain loadable by later versions.
+//!
+//! ```
+//! use counting_ratio::BayesianCounter;
+//!
+//! let mut bayesian = BayesianCounter::new();
+//! bayesian.observe(3, "odd".to_string());
+//! bayesian.observe(4, "even".to_string());
+//!
+//! let path = std::env::temp_dir().join("counting_ratio_persist_doctest.json");
+//! bayesian.save(&path).unwrap();
+//! let loaded: BayesianCounter<String, u32> = BayesianCounter::load(&path).unwrap();
+//! assert_eq!(loaded.count(3, "odd".to_string()), 1);
+//! assert_eq!(loaded.total(), 2);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use crate::{BayesianCounter, Countable};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum PersistError {
+    Io(std::io::Error),
+    Format(serde_json::Error),
+    UnsupportedVersion(u32),
+}
+
+impl Display for PersistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not access counter file: {e}"),
+            Self::Format(e) => write!(f, "malformed counter file: {e}"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported counter file format version {version}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Format(e) => Some(e),
+            Self::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PersistError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for PersistError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Format(e)
+    }
+}
+
+#[derive(Serialize)]
+struct SavedCounter<'a, L: Countable, S: Countable> {
+    format_version: u32,
+    counter: &'a BayesianCounter<L, S>,
+}
+
+#[derive(Deserialize)]
+struct LoadedCounter<L: Countable, S: Countable> {
+    format_version: u32,
+    counter: BayesianCounter<L, S>,
+}
+
+impl<L: Countable + Serialize, S: Countable + Serialize> BayesianCounter<L, S> {
+    /// Writes this counter to the file at `path`, replacing any existing file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PersistError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(
+            &mut writer,
+            &SavedCounter {
+                format_version: FORMAT_VERSION,
+                counter: self,
+            },
+        )?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<L: Countable + DeserializeOwned, S: Countable + DeserializeOwned> BayesianCounter<L, S> {
+    /// Reads a counter written by `save()` from the file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PersistError> {
+        let loaded: LoadedCounter<L, S> =
+            serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        if loaded.format_version > FORMAT_VERSION {
+            return Err(PersistError::UnsupportedVersion(loaded.format_version));
+        }
+        Ok(loaded.counter)
+    }
+}
@@ -0,0 +1,159 @@
+//! Charts of ratios drawn with `plotters`: a `RatioSeries` as a line of the percentage in
+//! each interval inside a 95% Wilson confidence band, and a `GroupedRatio` as one bar per
+//! group. The `draw_` functions accept a drawing area of any `plotters` backend, while the
+//! `_svg` functions render straight to an SVG string. Requires the `plot` feature.
+//!
+//! Only the SVG backend is enabled here. To render PNG images, enable the `bitmap_backend`,
+//! `bitmap_encoder`, and `ttf` features of your own `plotters` dependency and pass a
+//! `BitMapBackend` drawing area to the `draw_` functions.
+//!
+//! ```
+//! use counting_ratio::plot::{grouped_svg, series_svg};
+//! use counting_ratio::{CountingRatio, GroupedRatio, RatioSeries};
+//!
+//! let series: RatioSeries = (0..10)
+//!     .map(|minute| (minute * 60, CountingRatio::ratio(minute * 3, minute * 20)))
+//!     .collect();
+//! let svg = series_svg(&series, "Conversion rate", (640, 480)).unwrap();
+//! assert!(svg.starts_with("<svg"));
+//!
+//! let mut suites = GroupedRatio::new();
+//! suites.add("unit", CountingRatio::ratio(95, 100));
+//! suites.add("integration", CountingRatio::ratio(40, 50));
+//! let svg = grouped_svg(&suites, "Pass rate", (640, 480)).unwrap();
+//! assert!(svg.contains("integration"));
+//! ```
+
+use crate::{GroupedRatio, RatioSeries};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::fmt::Display;
+use std::io;
+
+const Z_95: f64 = 1.959964;
+
+/// Draws the percentage of the observations in each interval of `series` against the
+/// interval's end timestamp, inside a shaded 95% Wilson confidence band. Intervals without
+/// observations are skipped.
+pub fn draw_series<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    caption: &str,
+    series: &RatioSeries,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let points: Vec<(u64, f64, f64, f64)> = series
+        .intervals()
+        .into_iter()
+        .filter_map(|(timestamp, ratio)| {
+            let (low, high) = ratio.wilson_interval(Z_95);
+            Some((timestamp, 100.0 * ratio.value()?, 100.0 * low, 100.0 * high))
+        })
+        .collect();
+    let first = points.first().map_or(0, |point| point.0);
+    let last = points.last().map_or(1, |point| point.0.max(first + 1));
+
+    area.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(area)
+        .caption(caption, ("sans-serif", 20))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(first..last, 0.0..100.0)?;
+    chart
+        .configure_mesh()
+        .y_desc("%")
+        .y_label_formatter(&|percent| format!("{percent:.0}"))
+        .draw()?;
+
+    let band = points
+        .iter()
+        .map(|point| (point.0, point.3))
+        .chain(points.iter().rev().map(|point| (point.0, point.2)));
+    chart.draw_series(core::iter::once(Polygon::new(
+        band.collect::<Vec<_>>(),
+        BLUE.mix(0.2).filled(),
+    )))?;
+    chart.draw_series(LineSeries::new(
+        points.iter().map(|point| (point.0, point.1)),
+        &BLUE,
+    ))?;
+    area.present()
+}
+
+/// Draws one bar per group, as tall as the group's percentage, labeled with its key.
+/// Groups without observations get no bar.
+pub fn draw_grouped<DB: DrawingBackend, K: Ord + Display>(
+    area: &DrawingArea<DB, Shift>,
+    caption: &str,
+    grouped: &GroupedRatio<K>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let groups: Vec<(String, Option<f64>)> = grouped
+        .iter()
+        .map(|(key, ratio)| (key.to_string(), ratio.value()))
+        .collect();
+    let bars = groups.len().max(1);
+
+    area.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(area)
+        .caption(caption, ("sans-serif", 20))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d((0..bars).into_segmented(), 0.0..100.0)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(bars)
+        .x_label_formatter(&|segment| match segment {
+            SegmentValue::CenterOf(index) | SegmentValue::Exact(index) => groups
+                .get(*index)
+                .map_or_else(String::new, |group| group.0.clone()),
+            SegmentValue::Last => String::new(),
+        })
+        .y_desc("%")
+        .y_label_formatter(&|percent| format!("{percent:.0}"))
+        .draw()?;
+
+    chart.draw_series(groups.iter().enumerate().filter_map(|(index, group)| {
+        let percent = 100.0 * group.1?;
+        let mut bar = Rectangle::new(
+            [
+                (SegmentValue::Exact(index), 0.0),
+                (SegmentValue::Exact(index + 1), percent),
+            ],
+            BLUE.filled(),
+        );
+        bar.set_margin(0, 0, 5, 5);
+        Some(bar)
+    }))?;
+    area.present()
+}
+
+/// Renders `draw_series()` to an SVG document of the given `(width, height)`.
+pub fn series_svg(
+    series: &RatioSeries,
+    caption: &str,
+    size: (u32, u32),
+) -> Result<String, DrawingAreaErrorKind<io::Error>> {
+    let mut svg = String::new();
+    draw_series(
+        &SVGBackend::with_string(&mut svg, size).into_drawing_area(),
+        caption,
+        series,
+    )?;
+    Ok(svg)
+}
+
+/// Renders `draw_grouped()` to an SVG document of the given `(width, height)`.
+pub fn grouped_svg<K: Ord + Display>(
+    grouped: &GroupedRatio<K>,
+    caption: &str,
+    size: (u32, u32),
+) -> Result<String, DrawingAreaErrorKind<io::Error>> {
+    let mut svg = String::new();
+    draw_grouped(
+        &SVGBackend::with_string(&mut svg, size).into_drawing_area(),
+        caption,
+        grouped,
+    )?;
+    Ok(svg)
+}
@@ -0,0 +1,183 @@
+//! A `PosteriorRatio` combines observed counts with a Beta(`alpha`, `beta`) prior, treating
+//! the counts as a Beta-Binomial model. This gives sensible estimates even when only a
+//! handful of observations are available.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use counting_ratio::CountingRatio;
+//!
+//! let mut estimate = CountingRatio::with_prior(1.0, 1.0);
+//! estimate.observe(true);
+//! estimate.observe(true);
+//!
+//! assert_eq!(estimate.counts(), CountingRatio::ratio(2, 2));
+//! assert_eq!(estimate.posterior_mean(), 0.75);
+//!
+//! let (low, high) = estimate.credible_interval(0.95);
+//! assert!((low - 0.2924).abs() < 1e-4);
+//! assert!((high - 0.9916).abs() < 1e-4);
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use crate::CountingRatio;
+use core::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PosteriorRatio {
+    counts: CountingRatio,
+    alpha: f64,
+    beta: f64,
+}
+
+impl CountingRatio {
+    /// Starts counting from a Beta(`alpha`, `beta`) prior. Both parameters must be positive;
+    /// `(1.0, 1.0)` is the uniform prior.
+    pub fn with_prior(alpha: f64, beta: f64) -> PosteriorRatio {
+        CountingRatio::new().posterior(alpha, beta)
+    }
+
+    /// The probability that this ratio's true proportion exceeds that of `other`, treating
+    /// each as an independent Beta posterior under a uniform prior. This is the headline
+    /// figure of a Bayesian A/B test.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let treatment = CountingRatio::ratio(120, 1000);
+    /// let control = CountingRatio::ratio(100, 1000);
+    /// let p = treatment.prob_greater_than(&control);
+    /// assert!((p - 0.9232).abs() < 1e-4);
+    /// assert!((control.prob_greater_than(&treatment) - (1.0 - p)).abs() < 1e-9);
+    /// assert!((control.prob_greater_than(&control) - 0.5).abs() < 1e-9);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn prob_greater_than(&self, other: &CountingRatio) -> f64 {
+        self.posterior(1.0, 1.0)
+            .prob_greater_than(&other.posterior(1.0, 1.0))
+    }
+
+    /// The Bayes factor in favor of a proportion drawn from a Beta(`alpha`, `beta`) prior
+    /// over the point hypothesis that the proportion is exactly `p0`. Values above 1 favor
+    /// the Beta hypothesis, and values below 1 favor `p0`. The parameter `p0` must lie
+    /// strictly between 0 and 1, and both prior parameters must be positive.
+    ///
+    /// ```
+    /// use counting_ratio::CountingRatio;
+    ///
+    /// let heads = CountingRatio::ratio(62, 100);
+    /// assert!((heads.bayes_factor(0.5, 1.0, 1.0) - 2.2136).abs() < 1e-4);
+    ///
+    /// // Balanced counts favor the fair coin.
+    /// assert!(CountingRatio::ratio(50, 100).bayes_factor(0.5, 1.0, 1.0) < 0.15);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn bayes_factor(&self, p0: f64, alpha: f64, beta: f64) -> f64 {
+        self.ln_bayes_factor(p0, alpha, beta).exp()
+    }
+
+    /// The natural logarithm of `bayes_factor()`, which remains finite for counts large
+    /// enough that the factor itself would overflow.
+    #[cfg(feature = "std")]
+    pub fn ln_bayes_factor(&self, p0: f64, alpha: f64, beta: f64) -> f64 {
+        assert!(p0 > 0.0 && p0 < 1.0, "p0 must lie strictly between 0 and 1");
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        let (matches, observations) = self.counts_f64();
+        let misses = observations - matches;
+        crate::special::ln_beta(alpha + matches, beta + misses)
+            - crate::special::ln_beta(alpha, beta)
+            - matches * p0.ln()
+            - misses * (1.0 - p0).ln()
+    }
+
+    /// Combines the counts observed so far with a Beta(`alpha`, `beta`) prior.
+    pub fn posterior(self, alpha: f64, beta: f64) -> PosteriorRatio {
+        assert!(
+            alpha > 0.0 && beta > 0.0,
+            "Beta prior parameters must be positive"
+        );
+        PosteriorRatio {
+            counts: self,
+            alpha,
+            beta,
+        }
+    }
+}
+
+impl PosteriorRatio {
+    pub fn observe(&mut self, condition_met: bool) {
+        self.counts.observe(condition_met);
+    }
+
+    pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
+        self.counts
+            .observe_with_prior(prior_condition_met, posterior_condition_met);
+    }
+
+    /// The observed counts, without any contribution from the prior.
+    pub fn counts(&self) -> CountingRatio {
+        self.counts
+    }
+
+    pub fn prior(&self) -> (f64, f64) {
+        (self.alpha, self.beta)
+    }
+
+    /// The parameters of the Beta posterior distribution.
+    pub fn posterior_parameters(&self) -> (f64, f64) {
+        let (matches, observations) = self.counts.counts_f64();
+        (self.alpha + matches, self.beta + observations - matches)
+    }
+
+    pub fn posterior_mean(&self) -> f64 {
+        let (alpha, beta) = self.posterior_parameters();
+        alpha / (alpha + beta)
+    }
+
+    /// Returns the equal-tailed interval containing the true proportion with probability
+    /// `level` under the posterior distribution.
+    #[cfg(feature = "std")]
+    pub fn credible_interval(&self, level: f64) -> (f64, f64) {
+        let (alpha, beta) = self.posterior_parameters();
+        let tail = (1.0 - level) / 2.0;
+        (
+            crate::special::beta_quantile(tail, alpha, beta),
+            crate::special::beta_quantile(1.0 - tail, alpha, beta),
+        )
+    }
+
+    /// The probability that this posterior's proportion exceeds that of the independent
+    /// posterior `other`.
+    #[cfg(feature = "std")]
+    pub fn prob_greater_than(&self, other: &PosteriorRatio) -> f64 {
+        let (alpha, beta) = self.posterior_parameters();
+        let (other_alpha, other_beta) = other.posterior_parameters();
+        crate::special::beta_greater_than(alpha, beta, other_alpha, other_beta)
+    }
+}
+
+impl From<PosteriorRatio> for CountingRatio {
+    fn from(posterior: PosteriorRatio) -> Self {
+        posterior.counts
+    }
+}
+
+impl Display for PosteriorRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        write!(
+            f,
+            "{} with Beta({}, {}) prior (posterior mean {:.precision$}%)",
+            self.counts,
+            self.alpha,
+            self.beta,
+            100.0 * self.posterior_mean()
+        )
+    }
+}
@@ -0,0 +1,184 @@
+//! A `Probability` is an exact fraction between 0 and 1. Where the arithmetic of a
+//! `CountingRatio` operates on counts, a `Probability` follows the rules of probability:
+//! multiplying combines independent events, `complement()` gives the probability of the
+//! event not occurring, and `given()` conditions a joint probability on an event. The
+//! fraction is always kept in lowest terms, so equal probabilities compare equal.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, Probability};
+//!
+//! let heads = CountingRatio::ratio(5, 10).probability().unwrap();
+//! assert_eq!(heads, Probability::new(1, 2).unwrap());
+//!
+//! let two_heads = heads * heads;
+//! assert_eq!(two_heads, Probability::new(1, 4).unwrap());
+//! assert_eq!(two_heads.complement(), Probability::new(3, 4).unwrap());
+//! assert_eq!(two_heads.given(heads), Some(heads));
+//!
+//! assert_eq!(Probability::new(3, 2), None);
+//! assert_eq!(CountingRatio::new().probability(), None);
+//! assert_eq!(format!("{}", two_heads), "1/4");
+//! ```
+
+use crate::{gcd, CountingRatio};
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
+use core::ops::{Mul, MulAssign};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedProbability")
+)]
+pub struct Probability {
+    numerator: u64,
+    denominator: u64,
+}
+
+// Deserialized fractions are checked, and reduced, before becoming probabilities.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedProbability {
+    numerator: u64,
+    denominator: u64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedProbability> for Probability {
+    type Error = &'static str;
+
+    fn try_from(unchecked: UncheckedProbability) -> Result<Self, Self::Error> {
+        Self::new(unchecked.numerator, unchecked.denominator)
+            .ok_or("a probability needs a positive denominator no less than its numerator")
+    }
+}
+
+impl Probability {
+    /// The probability `numerator / denominator`, or `None` unless
+    /// `numerator <= denominator` and `denominator` is positive.
+    pub fn new(numerator: u64, denominator: u64) -> Option<Self> {
+        if denominator == 0 || numerator > denominator {
+            None
+        } else {
+            let common = gcd(numerator, denominator);
+            Some(Self {
+                numerator: numerator / common,
+                denominator: denominator / common,
+            })
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            numerator: 0,
+            denominator: 1,
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            numerator: 1,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    pub fn value(&self) -> f64 {
+        f64::from(CountingRatio::from(*self))
+    }
+
+    /// The probability that the event does not occur.
+    pub fn complement(self) -> Self {
+        Self {
+            numerator: self.denominator - self.numerator,
+            denominator: self.denominator,
+        }
+    }
+
+    /// Treating `self` as the probability that both this event and `condition` occur,
+    /// returns the probability of this event given `condition`. Returns `None` if
+    /// `condition` is impossible or less likely than `self`, which no joint probability
+    /// can be.
+    pub fn given(self, condition: Self) -> Option<Self> {
+        if condition.numerator == 0 {
+            return None;
+        }
+        let numerator = self.numerator.checked_mul(condition.denominator)?;
+        let denominator = self.denominator.checked_mul(condition.numerator)?;
+        Self::new(numerator, denominator)
+    }
+
+    /// The probability that two independent events both occur, or `None` if the
+    /// fraction overflows `u64`.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Cancelling across the fractions first keeps the products small.
+        let a = gcd(self.numerator, other.denominator);
+        let b = gcd(other.numerator, self.denominator);
+        Self::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+}
+
+impl CountingRatio {
+    /// The observed proportion as a `Probability`, or `None` if the ratio is undefined or
+    /// has more matches than observations.
+    pub fn probability(&self) -> Option<Probability> {
+        Probability::new(self.matches(), self.observations())
+    }
+}
+
+impl From<Probability> for CountingRatio {
+    fn from(probability: Probability) -> Self {
+        Self::ratio(probability.numerator, probability.denominator)
+    }
+}
+
+impl From<Probability> for f64 {
+    fn from(probability: Probability) -> Self {
+        probability.value()
+    }
+}
+
+/// Panics if the fraction overflows `u64`.
+impl Mul for Probability {
+    type Output = Probability;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("probability fraction overflows its count type")
+    }
+}
+
+impl MulAssign for Probability {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl PartialOrd for Probability {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Probability {
+    fn cmp(&self, other: &Self) -> Ordering {
+        CountingRatio::from(*self).cmp_value(&CountingRatio::from(*other))
+    }
+}
+
+impl Display for Probability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
@@ -0,0 +1,185 @@
+//! Prometheus counters that mirror a `CountingRatio` or `GroupedRatio`. Each ratio becomes a
+//! pair of monotonic counters, `<name>_matches_total` and `<name>_observations_total`, from
+//! which a query such as `rate(x_matches_total[5m]) / rate(x_observations_total[5m])`
+//! recovers the ratio. Both types implement `Collector`, so they can be registered with any
+//! `Registry`; clones share their counts. Requires the `prometheus` feature.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, PrometheusRatio};
+//! use prometheus::{Encoder, Registry, TextEncoder};
+//!
+//! let requests = PrometheusRatio::new("requests_ok", "Requests that succeeded").unwrap();
+//! let registry = Registry::new();
+//! registry.register(Box::new(requests.clone())).unwrap();
+//!
+//! requests.observe(true);
+//! requests.observe(false);
+//! requests.add(CountingRatio::ratio(8, 8));
+//! assert_eq!(requests.snapshot(), CountingRatio::ratio(9, 10));
+//!
+//! let mut text = Vec::new();
+//! TextEncoder::new().encode(&registry.gather(), &mut text).unwrap();
+//! let text = String::from_utf8(text).unwrap();
+//! assert!(text.contains("requests_ok_matches_total 9"));
+//! assert!(text.contains("requests_ok_observations_total 10"));
+//! ```
+
+use crate::{CountingRatio, GroupedRatio};
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{IntCounter, IntCounterVec, Opts};
+use std::sync::atomic::{fence, Ordering};
+
+#[derive(Clone, Debug)]
+pub struct PrometheusRatio {
+    matches: IntCounter,
+    observations: IntCounter,
+}
+
+impl PrometheusRatio {
+    /// `name` must be a valid Prometheus metric name, to which the counters append
+    /// `_matches_total` and `_observations_total`.
+    pub fn new(name: &str, help: &str) -> prometheus::Result<Self> {
+        Ok(Self {
+            matches: IntCounter::with_opts(matches_opts(name, help))?,
+            observations: IntCounter::with_opts(observations_opts(name, help))?,
+        })
+    }
+
+    // As in `AtomicCountingRatio`, observations are always incremented before matches, and
+    // reads take matches before observations, so neither a snapshot nor a scrape shows more
+    // matches than observations. The counters are relaxed atomics, so fences order them.
+    pub fn observe(&self, condition_met: bool) {
+        self.observations.inc();
+        if condition_met {
+            fence(Ordering::Release);
+            self.matches.inc();
+        }
+    }
+
+    /// Adds the counts of `ratio`, as when exporting observations counted elsewhere.
+    pub fn add(&self, ratio: CountingRatio) {
+        self.observations.inc_by(ratio.observations());
+        fence(Ordering::Release);
+        self.matches.inc_by(ratio.matches());
+    }
+
+    pub fn snapshot(&self) -> CountingRatio {
+        let matches = self.matches.get();
+        fence(Ordering::Acquire);
+        CountingRatio::ratio(matches, self.observations.get())
+    }
+}
+
+impl Collector for PrometheusRatio {
+    fn desc(&self) -> Vec<&Desc> {
+        self.matches
+            .desc()
+            .into_iter()
+            .chain(self.observations.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let mut families = self.matches.collect();
+        fence(Ordering::Acquire);
+        families.extend(self.observations.collect());
+        families
+    }
+}
+
+/// The counterpart of `PrometheusRatio` for a `GroupedRatio`, with one time series per key,
+/// distinguished by the `key` label.
+///
+/// ```
+/// use counting_ratio::{CountingRatio, GroupedRatio, PrometheusGroupedRatio};
+///
+/// let suites = PrometheusGroupedRatio::new("tests_passed", "Tests that passed").unwrap();
+/// suites.observe("unit", true);
+/// suites.observe("unit", false);
+///
+/// let mut grouped = GroupedRatio::new();
+/// grouped.add("integration", CountingRatio::ratio(4, 5));
+/// suites.add_grouped(&grouped);
+///
+/// assert_eq!(suites.get("unit"), CountingRatio::ratio(1, 2));
+/// assert_eq!(suites.get("integration"), CountingRatio::ratio(4, 5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrometheusGroupedRatio {
+    matches: IntCounterVec,
+    observations: IntCounterVec,
+}
+
+impl PrometheusGroupedRatio {
+    pub fn new(name: &str, help: &str) -> prometheus::Result<Self> {
+        Ok(Self {
+            matches: IntCounterVec::new(matches_opts(name, help), &["key"])?,
+            observations: IntCounterVec::new(observations_opts(name, help), &["key"])?,
+        })
+    }
+
+    // Ordered as in `PrometheusRatio`, one key at a time.
+    pub fn observe(&self, key: &str, condition_met: bool) {
+        self.observations.with_label_values(&[key]).inc();
+        if condition_met {
+            fence(Ordering::Release);
+            self.matches.with_label_values(&[key]).inc();
+        }
+    }
+
+    pub fn add(&self, key: &str, ratio: CountingRatio) {
+        self.observations
+            .with_label_values(&[key])
+            .inc_by(ratio.observations());
+        fence(Ordering::Release);
+        self.matches
+            .with_label_values(&[key])
+            .inc_by(ratio.matches());
+    }
+
+    /// Adds the ratio of every key of `grouped`.
+    pub fn add_grouped<K: Ord + ToString>(&self, grouped: &GroupedRatio<K>) {
+        for (key, ratio) in grouped.iter() {
+            self.add(&key.to_string(), ratio);
+        }
+    }
+
+    /// Returns the ratio for `key`, which is undefined if `key` was never observed.
+    pub fn get(&self, key: &str) -> CountingRatio {
+        let matches = self.matches.with_label_values(&[key]).get();
+        fence(Ordering::Acquire);
+        CountingRatio::ratio(matches, self.observations.with_label_values(&[key]).get())
+    }
+}
+
+impl Collector for PrometheusGroupedRatio {
+    fn desc(&self) -> Vec<&Desc> {
+        self.matches
+            .desc()
+            .into_iter()
+            .chain(self.observations.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let mut families = self.matches.collect();
+        fence(Ordering::Acquire);
+        families.extend(self.observations.collect());
+        families
+    }
+}
+
+fn matches_opts(name: &str, help: &str) -> Opts {
+    Opts::new(
+        format!("{name}_matches_total"),
+        format!("{help} (matching observations)"),
+    )
+}
+
+fn observations_opts(name: &str, help: &str) -> Opts {
+    Opts::new(
+        format!("{name}_observations_total"),
+        format!("{help} (all observations)"),
+    )
+}
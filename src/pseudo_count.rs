@@ -0,0 +1,96 @@
+//! A `PseudoCountRatio` starts from a number of imagined matches and observations, and
+//! counts real observations on top of them. This is additive smoothing, such as the
+//! "add one success and two trials" rule, which keeps the estimate away from 0% and 100%
+//! until enough observations arrive. The real counts remain available apart from the
+//! pseudo-counts.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let mut clicks = CountingRatio::with_pseudo_counts(1, 2);
+//! clicks.observe(false);
+//! clicks.observe(false);
+//!
+//! assert_eq!(clicks.raw_counts(), CountingRatio::ratio(0, 2));
+//! assert_eq!(clicks.pseudo_counts(), CountingRatio::ratio(1, 2));
+//! assert_eq!(clicks.combined(), CountingRatio::ratio(1, 4));
+//! assert_eq!(clicks.value(), Some(0.25));
+//! assert_eq!(format!("{clicks}"), "1/4 (25.00%) including 1/2 pseudo-counts");
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+use core::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PseudoCountRatio<T: Count = u64> {
+    counts: GenericCountingRatio<T>,
+    pseudo_counts: GenericCountingRatio<T>,
+}
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// Starts counting from `prior_matches` imagined matches among `prior_observations`
+    /// imagined observations. Panics if `prior_matches` exceeds `prior_observations`.
+    pub fn with_pseudo_counts(prior_matches: T, prior_observations: T) -> PseudoCountRatio<T> {
+        GenericCountingRatio::new().plus_pseudo_counts(prior_matches, prior_observations)
+    }
+
+    /// Combines the counts observed so far with `prior_matches` imagined matches among
+    /// `prior_observations` imagined observations. Panics if `prior_matches` exceeds
+    /// `prior_observations`.
+    pub fn plus_pseudo_counts(
+        self,
+        prior_matches: T,
+        prior_observations: T,
+    ) -> PseudoCountRatio<T> {
+        assert!(
+            prior_matches <= prior_observations,
+            "prior_matches must not exceed prior_observations"
+        );
+        PseudoCountRatio {
+            counts: self,
+            pseudo_counts: GenericCountingRatio::ratio(prior_matches, prior_observations),
+        }
+    }
+}
+
+impl<T: Count> PseudoCountRatio<T> {
+    pub fn observe(&mut self, condition_met: bool) {
+        self.counts.observe(condition_met);
+    }
+
+    pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
+        self.counts
+            .observe_with_prior(prior_condition_met, posterior_condition_met);
+    }
+
+    /// The observed counts, without the pseudo-counts.
+    pub fn raw_counts(&self) -> GenericCountingRatio<T> {
+        self.counts
+    }
+
+    pub fn pseudo_counts(&self) -> GenericCountingRatio<T> {
+        self.pseudo_counts
+    }
+
+    /// The observed counts plus the pseudo-counts.
+    pub fn combined(&self) -> GenericCountingRatio<T> {
+        self.counts + self.pseudo_counts
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.combined().value()
+    }
+}
+
+impl<T: Count> Display for PseudoCountRatio<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.combined(), f)?;
+        write!(
+            f,
+            " including {}/{} pseudo-counts",
+            self.pseudo_counts.matches(),
+            self.pseudo_counts.observations()
+        )
+    }
+}
@@ -0,0 +1,140 @@
+//! A `RatioTable` is the report at the end of many command-line tools: one named ratio per
+//! row, sorted by rate or by number of observations, with a row of totals beneath. Its
+//! `Display` form aligns the columns, and the formatter's precision sets the decimal places
+//! of the percentages.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, RatioTable};
+//!
+//! let mut table: RatioTable<&str> = [
+//!     ("parser", CountingRatio::ratio(45, 50)),
+//!     ("codegen", CountingRatio::ratio(8, 10)),
+//!     ("runtime", CountingRatio::ratio(970, 1000)),
+//! ]
+//! .into_iter()
+//! .collect();
+//! assert_eq!(table.total(), CountingRatio::ratio(1023, 1060));
+//!
+//! table.sort_by_rate();
+//! assert_eq!(
+//!     format!("{table}"),
+//!     "\
+//! runtime   970/1000  97.00%
+//! parser       45/50  90.00%
+//! codegen       8/10  80.00%
+//! --------------------------
+//! Total    1023/1060  96.51%
+//! "
+//! );
+//!
+//! table.sort_by_observations();
+//! assert_eq!(table.rows()[2].0, "codegen");
+//! ```
+
+use crate::CountingRatio;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatioTable<K> {
+    rows: Vec<(K, CountingRatio)>,
+}
+
+impl<K> RatioTable<K> {
+    pub fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Appends a row named `key`.
+    pub fn add(&mut self, key: K, ratio: CountingRatio) {
+        self.rows.push((key, ratio));
+    }
+
+    /// The rows in their current order.
+    pub fn rows(&self) -> &[(K, CountingRatio)] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The observations of every row combined.
+    pub fn total(&self) -> CountingRatio {
+        self.rows.iter().map(|(_, ratio)| ratio).sum()
+    }
+
+    /// Orders the rows from highest to lowest ratio, with undefined ratios last. Rows with
+    /// equal ratios keep their relative order.
+    pub fn sort_by_rate(&mut self) {
+        self.rows.sort_by(|(_, a), (_, b)| b.cmp_value(a));
+    }
+
+    /// Orders the rows from most to fewest observations. Rows with as many observations
+    /// keep their relative order.
+    pub fn sort_by_observations(&mut self) {
+        self.rows
+            .sort_by_key(|(_, ratio)| core::cmp::Reverse(ratio.observations()));
+    }
+}
+
+impl<K> Default for RatioTable<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> FromIterator<(K, CountingRatio)> for RatioTable<K> {
+    fn from_iter<I: IntoIterator<Item = (K, CountingRatio)>>(iter: I) -> Self {
+        Self {
+            rows: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<K> Extend<(K, CountingRatio)> for RatioTable<K> {
+    fn extend<I: IntoIterator<Item = (K, CountingRatio)>>(&mut self, iter: I) {
+        self.rows.extend(iter);
+    }
+}
+
+impl<K: Display> Display for RatioTable<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let total = self.total();
+        let lines: Vec<[String; 3]> = self
+            .rows
+            .iter()
+            .map(|(key, ratio)| (key.to_string(), ratio))
+            .chain(core::iter::once((String::from("Total"), &total)))
+            .map(|(name, ratio)| {
+                [
+                    name,
+                    format!("{}/{}", ratio.matches(), ratio.observations()),
+                    format!("{ratio:#.precision$}"),
+                ]
+            })
+            .collect();
+        let mut widths = [0; 3];
+        for line in lines.iter() {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let [w0, w1, w2] = widths;
+        for (index, [name, counts, percent]) in lines.iter().enumerate() {
+            if index + 1 == lines.len() {
+                writeln!(f, "{:-<width$}", "", width = w0 + w1 + w2 + 4)?;
+            }
+            writeln!(f, "{name:w0$}  {counts:>w1$}  {percent:>w2$}")?;
+        }
+        Ok(())
+    }
+}
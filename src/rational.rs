@@ -0,0 +1,48 @@
+//! Conversions between counting ratios and the exact fractions of `num_rational::Ratio`.
+//! Requires the `num-rational` feature. A `Ratio` is always in lowest terms, so converting
+//! 6/20 yields 3/10, and converting back cannot recover the original number of
+//! observations.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//! use num_rational::Ratio;
+//!
+//! let exact = Ratio::try_from(CountingRatio::ratio(6, 20)).unwrap();
+//! assert_eq!(exact, Ratio::new(3, 10));
+//! assert_eq!(exact * Ratio::new(1, 3), Ratio::new(1, 10));
+//! assert!(Ratio::try_from(CountingRatio::new()).is_err());
+//!
+//! assert_eq!(CountingRatio::try_from(Ratio::new(1, 4)), Ok(CountingRatio::ratio(1, 4)));
+//! assert!(CountingRatio::try_from(Ratio::new(5, 4)).is_err());
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+use num_rational::Ratio;
+
+/// Fails for an undefined ratio, returning it unchanged.
+impl<T: Count> TryFrom<GenericCountingRatio<T>> for Ratio<T> {
+    type Error = GenericCountingRatio<T>;
+
+    fn try_from(ratio: GenericCountingRatio<T>) -> Result<Self, Self::Error> {
+        if ratio.defined() {
+            let (numerator, denominator) = ratio.as_reduced();
+            Ok(Ratio::new_raw(numerator, denominator))
+        } else {
+            Err(ratio)
+        }
+    }
+}
+
+/// Fails for a fraction above one, or with a zero denominator, returning it unchanged.
+impl<T: Count> TryFrom<Ratio<T>> for GenericCountingRatio<T> {
+    type Error = Ratio<T>;
+
+    fn try_from(fraction: Ratio<T>) -> Result<Self, Self::Error> {
+        let (numerator, denominator) = (*fraction.numer(), *fraction.denom());
+        if denominator.is_zero() || numerator > denominator {
+            Err(fraction)
+        } else {
+            Ok(Self::ratio(numerator, denominator))
+        }
+    }
+}
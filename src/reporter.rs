@@ -0,0 +1,94 @@
+//! A `Reporter` logs snapshots of counters through `tracing`, either once an interval has
+//! elapsed, once a number of observations have been made, or both, whichever comes first.
+//! Each counter is logged as an `INFO` event with a `counter` field holding its name and a
+//! message holding its `Display` form. Requires the `tracing` feature.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, Reporter};
+//!
+//! let mut reporter = Reporter::every_observations(3);
+//! let mut hits = CountingRatio::new();
+//! let mut reports = 0;
+//! for i in 0..10 {
+//!     hits.observe(i % 4 != 0);
+//!     if reporter.tick(&[("cache.hits", &hits)]) {
+//!         reports += 1;
+//!     }
+//! }
+//! assert_eq!(reports, 3);
+//! ```
+
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub struct Reporter {
+    interval: Option<Duration>,
+    observations: Option<u64>,
+    last_report: Instant,
+    since_last_report: u64,
+}
+
+impl Reporter {
+    /// Reports once `interval` has elapsed since the previous report.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self::new(Some(interval), None)
+    }
+
+    /// Reports on every `observations`th tick, which must be positive.
+    pub fn every_observations(observations: u64) -> Self {
+        assert!(observations > 0, "observations must be positive");
+        Self::new(None, Some(observations))
+    }
+
+    /// Also reports on every `observations`th tick, whichever comes first.
+    pub fn or_every_observations(self, observations: u64) -> Self {
+        assert!(observations > 0, "observations must be positive");
+        Self {
+            observations: Some(observations),
+            ..self
+        }
+    }
+
+    /// Also reports once `interval` has elapsed, whichever comes first.
+    pub fn or_interval(self, interval: Duration) -> Self {
+        Self {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    fn new(interval: Option<Duration>, observations: Option<u64>) -> Self {
+        Self {
+            interval,
+            observations,
+            last_report: Instant::now(),
+            since_last_report: 0,
+        }
+    }
+
+    /// Counts one observation and reports `counters` if a report is due. Returns whether
+    /// it reported.
+    pub fn tick(&mut self, counters: &[(&str, &dyn Display)]) -> bool {
+        self.since_last_report += 1;
+        let due = self
+            .observations
+            .is_some_and(|every| self.since_last_report >= every)
+            || self
+                .interval
+                .is_some_and(|interval| self.last_report.elapsed() >= interval);
+        if due {
+            self.report(counters);
+        }
+        due
+    }
+
+    /// Reports `counters` immediately and restarts the schedule.
+    pub fn report(&mut self, counters: &[(&str, &dyn Display)]) {
+        for (name, counter) in counters {
+            tracing::info!(counter = name, "{counter}");
+        }
+        self.last_report = Instant::now();
+        self.since_last_report = 0;
+    }
+}
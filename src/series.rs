@@ -0,0 +1,125 @@
+//! A `RatioSeries` records timestamped snapshots of a cumulative `CountingRatio`, such as a
+//! conversion rate sampled once a minute. Because the snapshots are cumulative, the ratio
+//! of the observations made between any two timestamps is the difference of their
+//! snapshots. Timestamps are plain integers, as in `ErrorBudget`.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, RatioSeries};
+//!
+//! let mut conversions = RatioSeries::new();
+//! conversions.record(0, CountingRatio::ratio(0, 0));
+//! conversions.record(60, CountingRatio::ratio(5, 100));
+//! conversions.record(120, CountingRatio::ratio(20, 200));
+//! conversions.record(180, CountingRatio::ratio(24, 300));
+//!
+//! assert_eq!(conversions.at(150), CountingRatio::ratio(20, 200));
+//! assert_eq!(conversions.delta(60, 120), Some(CountingRatio::ratio(15, 100)));
+//! assert_eq!(
+//!     conversions.intervals(),
+//!     vec![
+//!         (60, CountingRatio::ratio(5, 100)),
+//!         (120, CountingRatio::ratio(15, 100)),
+//!         (180, CountingRatio::ratio(4, 100)),
+//!     ]
+//! );
+//!
+//! let coarse = conversions.downsample(120);
+//! assert_eq!(coarse.len(), 2);
+//! assert_eq!(coarse.latest(), Some((180, CountingRatio::ratio(24, 300))));
+//! ```
+
+use crate::CountingRatio;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatioSeries {
+    snapshots: Vec<(u64, CountingRatio)>,
+}
+
+impl RatioSeries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the cumulative `snapshot` taken at `timestamp`, which must not precede the
+    /// previous timestamp. A snapshot with the same timestamp as the previous one replaces
+    /// it.
+    pub fn record(&mut self, timestamp: u64, snapshot: CountingRatio) {
+        match self.snapshots.last_mut() {
+            Some((last, previous)) if *last == timestamp => *previous = snapshot,
+            Some((last, _)) if *last > timestamp => {
+                panic!("snapshots must be recorded in timestamp order")
+            }
+            _ => self.snapshots.push((timestamp, snapshot)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Iterates over every snapshot in timestamp order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, CountingRatio)> + '_ {
+        self.snapshots.iter().copied()
+    }
+
+    pub fn latest(&self) -> Option<(u64, CountingRatio)> {
+        self.snapshots.last().copied()
+    }
+
+    /// The most recent snapshot taken at or before `timestamp`, or an empty ratio if there
+    /// is none.
+    pub fn at(&self, timestamp: u64) -> CountingRatio {
+        let recorded = self.snapshots.partition_point(|(t, _)| *t <= timestamp);
+        if recorded == 0 {
+            CountingRatio::new()
+        } else {
+            self.snapshots[recorded - 1].1
+        }
+    }
+
+    /// The ratio of the observations made after `start` and up to `end`. Returns `None` if
+    /// the counts shrank between the two snapshots, as when the counter was reset.
+    pub fn delta(&self, start: u64, end: u64) -> Option<CountingRatio> {
+        self.at(end).checked_sub(self.at(start))
+    }
+
+    /// The ratio of the observations made between each snapshot and the one before it,
+    /// paired with the later timestamp. Intervals across which the counts shrank are
+    /// skipped.
+    pub fn intervals(&self) -> Vec<(u64, CountingRatio)> {
+        self.snapshots
+            .windows(2)
+            .filter_map(|pair| Some((pair[1].0, pair[1].1.checked_sub(pair[0].1)?)))
+            .collect()
+    }
+
+    /// Keeps only the last snapshot within each span of `width` timestamps, which must be
+    /// positive.
+    pub fn downsample(&self, width: u64) -> RatioSeries {
+        assert!(width > 0, "downsampling width must be positive");
+        let mut result = RatioSeries::new();
+        for (timestamp, snapshot) in self.iter() {
+            match result.snapshots.last_mut() {
+                Some(last) if last.0 / width == timestamp / width => *last = (timestamp, snapshot),
+                _ => result.snapshots.push((timestamp, snapshot)),
+            }
+        }
+        result
+    }
+}
+
+impl FromIterator<(u64, CountingRatio)> for RatioSeries {
+    fn from_iter<I: IntoIterator<Item = (u64, CountingRatio)>>(iter: I) -> Self {
+        let mut result = RatioSeries::new();
+        for (timestamp, snapshot) in iter {
+            result.record(timestamp, snapshot);
+        }
+        result
+    }
+}
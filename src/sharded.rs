@@ -0,0 +1,97 @@
+//! A `ShardedRatio` spreads its counts across several atomic sub-counters, each on its own
+//! cache line, so that threads observing at high rates rarely touch the same memory.
+//! Each thread consistently uses one shard, and `read()` adds the shards together. Compared
+//! to a single `AtomicCountingRatio`, observing is cheaper under contention while reading
+//! is costlier.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, ShardedRatio};
+//! use std::thread;
+//!
+//! let requests = ShardedRatio::with_shards(4);
+//! thread::scope(|scope| {
+//!     for _ in 0..8 {
+//!         scope.spawn(|| {
+//!             for i in 0..1000 {
+//!                 requests.observe(i % 10 != 0);
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! assert_eq!(requests.read(), CountingRatio::ratio(7200, 8000));
+//! ```
+
+use crate::{AtomicCountingRatio, CountingRatio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Aligned to keep neighboring shards off the same cache line, including on processors
+// that prefetch lines in adjacent pairs.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+struct Shard(AtomicCountingRatio);
+
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    static THREAD_INDEX: usize = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+#[derive(Debug)]
+pub struct ShardedRatio {
+    shards: Vec<Shard>,
+}
+
+impl ShardedRatio {
+    /// Creates one shard for each thread the system can run in parallel.
+    pub fn new() -> Self {
+        Self::with_shards(
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+        )
+    }
+
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "ShardedRatio needs at least one shard");
+        Self {
+            shards: (0..shards).map(|_| Shard::default()).collect(),
+        }
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    // Threads take shards in the order they first observe, so that up to `shards()`
+    // threads each have a shard to themselves.
+    fn shard(&self) -> &AtomicCountingRatio {
+        let index = THREAD_INDEX.with(|index| *index);
+        &self.shards[index % self.shards.len()].0
+    }
+
+    pub fn observe(&self, condition_met: bool) {
+        self.shard().observe(condition_met);
+    }
+
+    pub fn observe_with_prior(&self, prior_condition_met: bool, posterior_condition_met: bool) {
+        self.shard()
+            .observe_with_prior(prior_condition_met, posterior_condition_met);
+    }
+
+    pub fn add(&self, counts: CountingRatio) {
+        self.shard().add(counts);
+    }
+
+    /// Adds every shard together. Observations made while reading may or may not be
+    /// included.
+    pub fn read(&self) -> CountingRatio {
+        self.shards.iter().map(|shard| shard.0.snapshot()).sum()
+    }
+}
+
+impl Default for ShardedRatio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,156 @@
+//! Synthetic observation streams drawn from known probabilities, for demonstrations and for
+//! checking how quickly a detector notices a shift. Requires the `rand` feature.
+//!
+//! ```
+//! use counting_ratio::simulate::{sample_ratio, shifted, LabeledSource};
+//! use counting_ratio::{BayesianCounter, CusumChart};
+//! use rand::rngs::StdRng;
+//! use rand::SeedableRng;
+//!
+//! let mut rng = StdRng::seed_from_u64(42);
+//! let heads = sample_ratio(&mut rng, 0.3, 10_000);
+//! assert!((heads.value().unwrap() - 0.3).abs() < 0.02);
+//!
+//! // How long does a CUSUM chart take to notice a shift from 10% to 30%?
+//! let mut chart = CusumChart::new(0.1, 0.05, 4.0);
+//! let signal = shifted(&mut rng, 0.1, 0.3, 500)
+//!     .take(1000)
+//!     .position(|defective| chart.observe(defective).is_some());
+//! assert!(signal.is_some_and(|at| at >= 500));
+//!
+//! let source = LabeledSource::new()
+//!     .with_label("spam", 1.0, [("buy", 0.8), ("hello", 0.2)])
+//!     .with_label("ham", 3.0, [("buy", 0.1), ("hello", 0.9)]);
+//! let mut counter = BayesianCounter::new();
+//! source.feed(&mut rng, &mut counter, 10_000);
+//! assert!((f64::from(counter.p_label("spam")) - 0.25).abs() < 0.02);
+//! assert_eq!(counter.classify("buy"), Some("spam"));
+//! ```
+
+use crate::{BayesianCounter, Countable, CountingRatio};
+use rand::Rng;
+
+fn assert_probability(p: f64) {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "probability must lie within [0.0, 1.0]"
+    );
+}
+
+/// An endless stream in which each observation meets the condition with probability `p`.
+pub fn bernoulli<R: Rng + ?Sized>(rng: &mut R, p: f64) -> impl Iterator<Item = bool> + '_ {
+    assert_probability(p);
+    core::iter::repeat_with(move || rng.random_bool(p))
+}
+
+/// An endless stream whose probability changes from `before` to `after` once `change_at`
+/// observations have been made.
+pub fn shifted<R: Rng + ?Sized>(
+    rng: &mut R,
+    before: f64,
+    after: f64,
+    change_at: usize,
+) -> impl Iterator<Item = bool> + '_ {
+    assert_probability(before);
+    assert_probability(after);
+    (0..).map(move |i| rng.random_bool(if i < change_at { before } else { after }))
+}
+
+/// Counts `observations` independent observations that each meet the condition with
+/// probability `p`.
+pub fn sample_ratio<R: Rng + ?Sized>(rng: &mut R, p: f64, observations: u64) -> CountingRatio {
+    let mut ratio = CountingRatio::new();
+    for condition_met in bernoulli(rng, p).take(observations as usize) {
+        ratio.observe(condition_met);
+    }
+    ratio
+}
+
+/// Generates labeled examples: a label is drawn in proportion to its weight, and then one
+/// of that label's examples in proportion to the example weights.
+#[derive(Clone, Debug)]
+pub struct LabeledSource<L, S> {
+    labels: Vec<(L, f64)>,
+    // The weighted examples of each label, in the same order as the labels.
+    examples: Vec<Vec<(S, f64)>>,
+}
+
+impl<L: Countable, S: Countable> LabeledSource<L, S> {
+    pub fn new() -> Self {
+        Self {
+            labels: Vec::new(),
+            examples: Vec::new(),
+        }
+    }
+
+    /// Adds `label` with relative frequency `weight` and the relative frequencies of its
+    /// `examples`. Weights must not be negative, and each list of weights must have a
+    /// positive total.
+    pub fn with_label<I: IntoIterator<Item = (S, f64)>>(
+        mut self,
+        label: L,
+        weight: f64,
+        examples: I,
+    ) -> Self {
+        let examples: Vec<(S, f64)> = examples.into_iter().collect();
+        assert!(weight >= 0.0, "label weight must not be negative");
+        assert!(
+            examples.iter().all(|(_, weight)| *weight >= 0.0)
+                && examples.iter().map(|(_, weight)| weight).sum::<f64>() > 0.0,
+            "example weights must not be negative and must have a positive total"
+        );
+        self.labels.push((label, weight));
+        self.examples.push(examples);
+        self
+    }
+
+    /// Draws one `(example, label)` pair. Panics if no label has a positive weight.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (S, L) {
+        let label =
+            choose(rng, &self.labels).expect("at least one label must have a positive weight");
+        let examples = &self.examples[label];
+        let example = choose(rng, examples).expect("example weights are positive");
+        (examples[example].0.clone(), self.labels[label].0.clone())
+    }
+
+    /// Draws `observations` pairs and records each in `counter`.
+    pub fn feed<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        counter: &mut BayesianCounter<L, S>,
+        observations: u64,
+    ) {
+        for _ in 0..observations {
+            let (example, label) = self.sample(rng);
+            counter.observe(example, label);
+        }
+    }
+}
+
+impl<L: Countable, S: Countable> Default for LabeledSource<L, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The index of an item drawn in proportion to its weight, or `None` if no weight is
+// positive.
+fn choose<T, R: Rng + ?Sized>(rng: &mut R, items: &[(T, f64)]) -> Option<usize> {
+    let total: f64 = items.iter().map(|(_, weight)| weight).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut remaining = rng.random::<f64>() * total;
+    let mut last = None;
+    for (index, (_, weight)) in items.iter().enumerate() {
+        if *weight > 0.0 {
+            remaining -= weight;
+            if remaining < 0.0 {
+                return Some(index);
+            }
+            last = Some(index);
+        }
+    }
+    // Rounding can leave a sliver of weight past the final item.
+    last
+}
@@ -0,0 +1,280 @@
+//! A `SketchCounter` approximates the counts of a `BayesianCounter` in bounded memory. Each
+//! label keeps a count-min sketch of its examples, so memory depends only on the number of
+//! labels and the dimensions of the sketch, never on the number of distinct examples.
+//! Estimated counts never fall below the true counts, and exceed them by at most
+//! `epsilon` times the label's total with probability at least `1 - delta`. Examples are
+//! hashed with a fixed function, so a serialized sketch gives the same estimates when loaded
+//! by any build. Requires the `sketch` feature.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, SketchCounter};
+//!
+//! let mut sketch = SketchCounter::with_error(0.001, 0.01);
+//! for i in 0..10_000u64 {
+//!     sketch.observe(i % 100, i % 2 == 0);
+//! }
+//!
+//! assert!(sketch.count(42, true) >= 100);
+//! assert!(sketch.count(42, true) <= 100 + 5);
+//! assert_eq!(sketch.label_count(true), 5000);
+//! assert_eq!(sketch.p_label(true), CountingRatio::ratio(5000, 10_000));
+//! ```
+
+use crate::{Countable, CountingRatio};
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SketchCounter<L: Countable, S: Hash> {
+    width: usize,
+    depth: usize,
+    labels: BTreeMap<L, CountMinSketch>,
+    examples: CountMinSketch,
+    total: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    example_type: PhantomData<fn(S)>,
+}
+
+impl<L: Countable, S: Hash> SketchCounter<L, S> {
+    /// Each label's sketch holds `depth` rows of `width` counters. Both must be positive.
+    pub fn new(width: usize, depth: usize) -> Self {
+        assert!(width > 0 && depth > 0, "sketch dimensions must be positive");
+        Self {
+            width,
+            depth,
+            labels: BTreeMap::new(),
+            examples: CountMinSketch::new(width, depth),
+            total: 0,
+            example_type: PhantomData,
+        }
+    }
+
+    /// Sizes each sketch so that an estimated count exceeds the true count by at most
+    /// `epsilon` times the label's total, with probability at least `1 - delta`. Both
+    /// parameters must lie strictly between 0 and 1.
+    pub fn with_error(epsilon: f64, delta: f64) -> Self {
+        assert!(
+            0.0 < epsilon && epsilon < 1.0 && 0.0 < delta && delta < 1.0,
+            "epsilon and delta must lie strictly between 0 and 1"
+        );
+        Self::new(
+            (core::f64::consts::E / epsilon).ceil() as usize,
+            (1.0 / delta).ln().ceil() as usize,
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn observe(&mut self, example: S, label: L) {
+        let (width, depth) = (self.width, self.depth);
+        self.labels
+            .entry(label)
+            .or_insert_with(|| CountMinSketch::new(width, depth))
+            .add(&example);
+        self.examples.add(&example);
+        self.total += 1;
+    }
+
+    /// Adds every count of `other`, which must have the same dimensions, to this counter.
+    pub fn merge(&mut self, other: &Self) {
+        assert!(
+            self.width == other.width && self.depth == other.depth,
+            "only sketches with the same dimensions can be merged"
+        );
+        for (label, sketch) in other.labels.iter() {
+            match self.labels.get_mut(label) {
+                Some(mine) => mine.merge(sketch),
+                None => {
+                    self.labels.insert(label.clone(), sketch.clone());
+                }
+            }
+        }
+        self.examples.merge(&other.examples);
+        self.total += other.total;
+    }
+
+    /// An estimate of the number of times `example` was observed with `label`. It is never
+    /// less than the true count.
+    pub fn count(&self, example: S, label: L) -> u64 {
+        self.labels
+            .get(&label)
+            .map_or(0, |sketch| sketch.estimate(&example))
+    }
+
+    /// The exact number of observations of `label`.
+    pub fn label_count(&self, label: L) -> u64 {
+        self.labels.get(&label).map_or(0, |sketch| sketch.total)
+    }
+
+    /// An estimate of the number of times `example` was observed under any label.
+    pub fn example_count(&self, example: S) -> u64 {
+        self.examples.estimate(&example)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Every label observed so far, in ascending order.
+    pub fn labels(&self) -> impl Iterator<Item = L> + '_ {
+        self.labels.keys().cloned()
+    }
+
+    pub fn p_label(&self, label: L) -> CountingRatio {
+        CountingRatio::ratio(self.label_count(label), self.total)
+    }
+
+    pub fn p_example(&self, example: S) -> CountingRatio {
+        CountingRatio::ratio(self.example_count(example), self.total)
+    }
+
+    pub fn p_example_given_label(&self, example: S, label: L) -> CountingRatio {
+        self.p_example_given_label_of(&example, &label)
+    }
+
+    fn p_example_given_label_of(&self, example: &S, label: &L) -> CountingRatio {
+        CountingRatio::ratio(
+            self.labels
+                .get(label)
+                .map_or(0, |sketch| sketch.estimate(example)),
+            self.labels.get(label).map_or(0, |sketch| sketch.total),
+        )
+    }
+
+    pub fn p_label_given_example(&self, label: L, example: S) -> CountingRatio {
+        let p_example = CountingRatio::ratio(self.examples.estimate(&example), self.total);
+        self.p_example_given_label_of(&example, &label) * self.p_label(label) / p_example
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct CountMinSketch {
+    width: usize,
+    counters: Vec<u64>,
+    total: u64,
+}
+
+impl CountMinSketch {
+    fn new(width: usize, depth: usize) -> Self {
+        Self {
+            width,
+            counters: vec![0; width * depth],
+            total: 0,
+        }
+    }
+
+    // The index of the counter for `item` within each row. Every row seeds its hasher
+    // with the row number, which makes the rows' hash functions independent.
+    fn cells<'a, S: Hash>(&'a self, item: &'a S) -> impl Iterator<Item = usize> + 'a {
+        (0..self.counters.len() / self.width).map(move |row| {
+            let mut hasher = StableHasher::new();
+            (row as u64).hash(&mut hasher);
+            item.hash(&mut hasher);
+            row * self.width + (hasher.finish() % self.width as u64) as usize
+        })
+    }
+
+    fn add<S: Hash>(&mut self, item: &S) {
+        let cells: Vec<usize> = self.cells(item).collect();
+        for cell in cells {
+            self.counters[cell] += 1;
+        }
+        self.total += 1;
+    }
+
+    fn estimate<S: Hash>(&self, item: &S) -> u64 {
+        self.cells(item)
+            .map(|cell| self.counters[cell])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (mine, theirs) in self.counters.iter_mut().zip(other.counters.iter()) {
+            *mine += *theirs;
+        }
+        self.total += other.total;
+    }
+}
+
+// 64-bit FNV-1a, with integers written as little-endian bytes and `usize` widened to 64
+// bits, then the finalizer of MurmurHash3 to spread the bits the counter index depends on.
+// Unlike `DefaultHasher`, its output never changes between Rust releases or platforms, so
+// a serialized sketch stays valid wherever it is loaded, as long as the example type's
+// `Hash` implementation stays the same.
+struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^ (h >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+}
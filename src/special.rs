@@ -0,0 +1,227 @@
+// Special functions underlying the crate's interval estimates and hypothesis tests.
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+const EPSILON: f64 = 1e-15;
+const MAX_ITERATIONS: usize = 1000;
+
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula
+        core::f64::consts::PI.ln() - (core::f64::consts::PI * x).sin().ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + LANCZOS_G + 0.5;
+        let series = LANCZOS_COEFFICIENTS
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold(LANCZOS_COEFFICIENTS[0], |sum, (i, c)| {
+                sum + c / (x + i as f64)
+            });
+        0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+    }
+}
+
+pub(crate) fn ln_beta(a: f64, b: f64) -> f64 {
+    ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b)
+}
+
+/// The regularized incomplete beta function I_x(a, b), which is the CDF of Beta(a, b).
+pub(crate) fn beta_cdf(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = (a * x.ln() + b * (1.0 - x).ln() - ln_beta(a, b)).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+// Evaluates the continued fraction for the incomplete beta function by the modified
+// Lentz method.
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        for numerator in [
+            even,
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            result *= d * c;
+        }
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    result
+}
+
+/// The inverse of `beta_cdf()` in `x`, found by bisection.
+pub(crate) fn beta_quantile(p: f64, a: f64, b: f64) -> f64 {
+    bisect(0.0, 1.0, |x| beta_cdf(x, a, b) < p)
+}
+
+// Finds the boundary within [low, high] where `below` switches from true to false.
+pub(crate) fn bisect<F: Fn(f64) -> bool>(mut low: f64, mut high: f64, below: F) -> f64 {
+    for _ in 0..200 {
+        let middle = 0.5 * (low + high);
+        if middle <= low || middle >= high {
+            break;
+        }
+        if below(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// The regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x).
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    let mut denominator = a;
+    for _ in 0..MAX_ITERATIONS {
+        denominator += 1.0;
+        term *= x / denominator;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut result = d;
+    for i in 1..=MAX_ITERATIONS {
+        let i = i as f64;
+        let numerator = -i * (i - a);
+        b += 2.0;
+        d = numerator * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + numerator / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        result *= d * c;
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * result
+}
+
+/// The probability that a standard normal variable exceeds `|z|` in either direction.
+pub(crate) fn normal_two_sided_p(z: f64) -> f64 {
+    // erfc(|z| / sqrt(2)) = Q(1/2, z^2 / 2)
+    gamma_q(0.5, z * z / 2.0)
+}
+
+/// The probability that X ~ Beta(a1, b1) exceeds an independent Y ~ Beta(a2, b2).
+pub(crate) fn beta_greater_than(a1: f64, b1: f64, a2: f64, b2: f64) -> f64 {
+    // The closed form sums one term per unit of an integral first parameter.
+    let integral = |a: f64| a.fract() == 0.0 && a <= 1e6;
+    if integral(a1) && (a1 <= a2 || !integral(a2)) {
+        beta_greater_sum(a1, b1, a2, b2)
+    } else if integral(a2) {
+        1.0 - beta_greater_sum(a2, b2, a1, b1)
+    } else {
+        beta_greater_integral(a1, b1, a2, b2)
+    }
+}
+
+// P(X > Y) = sum over i < a1 of B(a2 + i, b1 + b2) / ((b1 + i) B(1 + i, b1) B(a2, b2)),
+// valid when a1 is a positive integer.
+fn beta_greater_sum(a1: f64, b1: f64, a2: f64, b2: f64) -> f64 {
+    let mut total = 0.0;
+    for i in 0..a1 as u64 {
+        let i = i as f64;
+        total +=
+            (ln_beta(a2 + i, b1 + b2) - (b1 + i).ln() - ln_beta(1.0 + i, b1) - ln_beta(a2, b2))
+                .exp();
+    }
+    total.clamp(0.0, 1.0)
+}
+
+// P(X > Y) as the integral of the density of X times the CDF of Y, by the midpoint rule,
+// which never evaluates a density at an endpoint where it may be infinite.
+fn beta_greater_integral(a1: f64, b1: f64, a2: f64, b2: f64) -> f64 {
+    const STEPS: usize = 10_000;
+    let width = 1.0 / STEPS as f64;
+    let ln_normalizer = ln_beta(a1, b1);
+    let mut total = 0.0;
+    for step in 0..STEPS {
+        let x = (step as f64 + 0.5) * width;
+        let density = ((a1 - 1.0) * x.ln() + (b1 - 1.0) * (1.0 - x).ln() - ln_normalizer).exp();
+        total += density * beta_cdf(x, a2, b2) * width;
+    }
+    total.clamp(0.0, 1.0)
+}
+
+/// The CDF of the standard normal distribution.
+pub(crate) fn normal_cdf(z: f64) -> f64 {
+    let upper_tail = normal_two_sided_p(z) / 2.0;
+    if z >= 0.0 {
+        1.0 - upper_tail
+    } else {
+        upper_tail
+    }
+}
+
+/// The inverse of `normal_cdf()`, found by bisection.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    bisect(-40.0, 40.0, |z| normal_cdf(z) < p)
+}
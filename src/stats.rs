@@ -0,0 +1,81 @@
+//! Planning calculations for comparing two proportions with the two-sided, two-proportion
+//! z-test of `CountingRatio::z_test()`: how many observations each group needs to detect
+//! a given difference, and how likely a given number of observations is to detect it.
+//!
+//! ```
+//! use counting_ratio::stats::{minimum_detectable_effect, power, required_sample_size};
+//!
+//! // Detecting a lift from a 10% to a 12% conversion rate.
+//! let n = required_sample_size(0.10, 0.02, 0.05, 0.8);
+//! assert_eq!(n, 3841);
+//! assert!((power(0.10, 0.02, 0.05, n) - 0.8).abs() < 1e-3);
+//! assert!(power(0.10, 0.02, 0.05, 1000) < 0.3);
+//!
+//! let mde = minimum_detectable_effect(0.10, 0.05, 0.8, n);
+//! assert!((mde - 0.02).abs() < 1e-4);
+//! ```
+
+use crate::special::{bisect, normal_cdf, normal_quantile};
+
+fn check_plan(baseline: f64, effect: f64, alpha: f64) {
+    assert!(
+        baseline > 0.0 && baseline < 1.0,
+        "baseline must lie strictly between 0 and 1"
+    );
+    assert!(
+        effect != 0.0 && baseline + effect > 0.0 && baseline + effect < 1.0,
+        "effect must be nonzero and keep the baseline plus effect strictly between 0 and 1"
+    );
+    assert!(
+        alpha > 0.0 && alpha < 1.0,
+        "significance level must lie strictly between 0 and 1"
+    );
+}
+
+fn check_power(power: f64) {
+    assert!(
+        power > 0.0 && power < 1.0,
+        "power must lie strictly between 0 and 1"
+    );
+}
+
+// The standard deviations of a single observation's contribution to the difference,
+// under the null hypothesis and under the alternative.
+fn deviations(baseline: f64, effect: f64) -> (f64, f64) {
+    let treatment = baseline + effect;
+    let pooled = baseline + effect / 2.0;
+    (
+        (2.0 * pooled * (1.0 - pooled)).sqrt(),
+        (baseline * (1.0 - baseline) + treatment * (1.0 - treatment)).sqrt(),
+    )
+}
+
+/// The number of observations each group needs for a test at significance level `alpha`
+/// to detect a change of `effect` from the `baseline` proportion with probability `power`.
+pub fn required_sample_size(baseline: f64, effect: f64, alpha: f64, power: f64) -> u64 {
+    check_plan(baseline, effect, alpha);
+    check_power(power);
+    let (null, alternative) = deviations(baseline, effect);
+    let z = normal_quantile(1.0 - alpha / 2.0) * null + normal_quantile(power) * alternative;
+    (z * z / (effect * effect)).ceil() as u64
+}
+
+/// The probability that a test at significance level `alpha`, with `observations` in each
+/// group, detects a change of `effect` from the `baseline` proportion.
+pub fn power(baseline: f64, effect: f64, alpha: f64, observations: u64) -> f64 {
+    check_plan(baseline, effect, alpha);
+    let (null, alternative) = deviations(baseline, effect);
+    let z = effect.abs() * (observations as f64).sqrt() - normal_quantile(1.0 - alpha / 2.0) * null;
+    normal_cdf(z / alternative)
+}
+
+/// The smallest increase from the `baseline` proportion that `observations` in each group
+/// detect with probability `power` at significance level `alpha`. Returns the largest
+/// possible increase if even that is not detected reliably.
+pub fn minimum_detectable_effect(baseline: f64, alpha: f64, power: f64, observations: u64) -> f64 {
+    check_plan(baseline, (1.0 - baseline) / 2.0, alpha);
+    check_power(power);
+    bisect(0.0, 1.0 - baseline, |effect| {
+        self::power(baseline, effect, alpha, observations) < power
+    })
+}
@@ -0,0 +1,219 @@
+//! A `StrictRatio` is a counting ratio that can never hold more matches than observations,
+//! nor a count that has overflowed. Every operation that could break either rule returns a
+//! `RatioError` instead of producing a nonsensical state, such as a proportion of 350%. The
+//! arithmetic operators therefore yield a `Result`, and there are no assigning operators.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, RatioError, StrictRatio};
+//!
+//! let mut passed = StrictRatio::new();
+//! for outcome in [true, true, false] {
+//!     passed.observe(outcome).unwrap();
+//! }
+//! let earlier = StrictRatio::try_from(CountingRatio::ratio(5, 7)).unwrap();
+//! let total = (passed + earlier).unwrap();
+//! assert_eq!(total.counts(), CountingRatio::ratio(7, 10));
+//!
+//! assert_eq!(
+//!     StrictRatio::try_from(CountingRatio::ratio(7, 2)),
+//!     Err(RatioError::MatchesExceedObservations)
+//! );
+//! assert_eq!(
+//!     total - StrictRatio::try_ratio(0, 8).unwrap(),
+//!     Err(RatioError::MatchesExceedObservations)
+//! );
+//!
+//! let huge: StrictRatio = StrictRatio::try_ratio(1, u64::MAX).unwrap();
+//! assert_eq!(huge * 2, Err(RatioError::Overflow));
+//! ```
+
+use crate::{Count, GenericCountingRatio, RatioError};
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, Div, Mul, Sub};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "GenericCountingRatio<T>", into = "GenericCountingRatio<T>")
+)]
+pub struct StrictRatio<T: Count = u64> {
+    ratio: GenericCountingRatio<T>,
+}
+
+impl<T: Count> StrictRatio<T> {
+    pub fn new() -> Self {
+        Self {
+            ratio: GenericCountingRatio::new(),
+        }
+    }
+
+    /// As `GenericCountingRatio::try_ratio()`, checking counts of any integer type.
+    pub fn try_ratio<M: TryInto<T>, O: TryInto<T>>(
+        matches: M,
+        observations: O,
+    ) -> Result<Self, RatioError> {
+        GenericCountingRatio::try_ratio(matches, observations).map(|ratio| Self { ratio })
+    }
+
+    /// The underlying ratio, for the read-only methods of `GenericCountingRatio`.
+    pub fn counts(&self) -> GenericCountingRatio<T> {
+        self.ratio
+    }
+
+    pub fn matches(&self) -> T {
+        self.ratio.matches()
+    }
+
+    pub fn observations(&self) -> T {
+        self.ratio.observations()
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.ratio.value()
+    }
+
+    pub fn observe(&mut self, condition_met: bool) -> Result<(), RatioError> {
+        let matches = if condition_met { T::one() } else { T::zero() };
+        self.observe_batch(matches, T::one())
+    }
+
+    pub fn observe_with_prior(
+        &mut self,
+        prior_condition_met: bool,
+        posterior_condition_met: bool,
+    ) -> Result<(), RatioError> {
+        if prior_condition_met {
+            self.observe(posterior_condition_met)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records `matches` among `observations` new observations. Fails, leaving the ratio
+    /// unchanged, if the batch has more matches than observations or a count overflows.
+    pub fn observe_batch(&mut self, matches: T, observations: T) -> Result<(), RatioError> {
+        let batch = Self::try_ratio(matches, observations)?;
+        *self = self.checked_add(batch)?;
+        Ok(())
+    }
+
+    /// Combines the observations of both ratios, failing if a count overflows.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, RatioError> {
+        Self::checked(
+            self.matches().checked_add(&rhs.matches()),
+            self.observations().checked_add(&rhs.observations()),
+        )
+    }
+
+    /// Retracts the observations in `rhs`, failing if `rhs` holds more matches or more
+    /// observations than `self`, or if the remaining matches would exceed the remaining
+    /// observations.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, RatioError> {
+        Self::checked(
+            self.matches().checked_sub(&rhs.matches()),
+            self.observations().checked_sub(&rhs.observations()),
+        )
+    }
+
+    /// Multiplies the matches and the observations of both ratios, as `Mul` does for
+    /// `GenericCountingRatio`, failing if a count overflows.
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, RatioError> {
+        Self::checked(
+            self.matches().checked_mul(&rhs.matches()),
+            self.observations().checked_mul(&rhs.observations()),
+        )
+    }
+
+    /// Divides as `Div` does for `GenericCountingRatio`, failing if a count overflows or if
+    /// the quotient exceeds one.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, RatioError> {
+        Self::checked(
+            self.matches().checked_mul(&rhs.observations()),
+            self.observations().checked_mul(&rhs.matches()),
+        )
+    }
+
+    /// Multiplies both counts by `factor`, as `GenericCountingRatio::scale()` does, failing
+    /// if a count overflows.
+    pub fn checked_scale(self, factor: T) -> Result<Self, RatioError> {
+        Self::checked(
+            self.matches().checked_mul(&factor),
+            self.observations().checked_mul(&factor),
+        )
+    }
+
+    // A missing count signals overflow.
+    fn checked(matches: Option<T>, observations: Option<T>) -> Result<Self, RatioError> {
+        match (matches, observations) {
+            (Some(matches), Some(observations)) => Self::try_ratio(matches, observations),
+            _ => Err(RatioError::Overflow),
+        }
+    }
+}
+
+impl<T: Count> Default for StrictRatio<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Count> TryFrom<GenericCountingRatio<T>> for StrictRatio<T> {
+    type Error = RatioError;
+
+    fn try_from(ratio: GenericCountingRatio<T>) -> Result<Self, Self::Error> {
+        Self::try_ratio(ratio.matches(), ratio.observations())
+    }
+}
+
+impl<T: Count> From<StrictRatio<T>> for GenericCountingRatio<T> {
+    fn from(strict: StrictRatio<T>) -> Self {
+        strict.ratio
+    }
+}
+
+impl<T: Count> Display for StrictRatio<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.ratio, f)
+    }
+}
+
+impl<T: Count> Add for StrictRatio<T> {
+    type Output = Result<Self, RatioError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+    }
+}
+
+impl<T: Count> Sub for StrictRatio<T> {
+    type Output = Result<Self, RatioError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+    }
+}
+
+impl<T: Count> Mul for StrictRatio<T> {
+    type Output = Result<Self, RatioError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+    }
+}
+
+impl<T: Count> Mul<T> for StrictRatio<T> {
+    type Output = Result<Self, RatioError>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.checked_scale(rhs)
+    }
+}
+
+impl<T: Count> Div for StrictRatio<T> {
+    type Output = Result<Self, RatioError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+    }
+}
@@ -0,0 +1,104 @@
+//! A `SuccessTracker` decides whether a dependency is healthy, as a circuit breaker does,
+//! from the success rate of its most recent calls. Once the rate falls below a threshold the
+//! tracker reports unhealthy, and it only reports healthy again once the rate climbs above
+//! the threshold plus a hysteresis margin, so that a rate hovering near the threshold does
+//! not make it flap.
+//!
+//! ```
+//! use counting_ratio::SuccessTracker;
+//!
+//! let mut tracker = SuccessTracker::new(10).with_hysteresis(0.2);
+//! for _ in 0..10 {
+//!     tracker.record_success();
+//! }
+//! assert!(tracker.is_healthy(0.7));
+//!
+//! for _ in 0..4 {
+//!     tracker.record_failure();
+//! }
+//! assert!(!tracker.is_healthy(0.7));
+//!
+//! // 7 of the last 10 calls succeeded, which meets the threshold but not the margin.
+//! for _ in 0..7 {
+//!     tracker.record_success();
+//! }
+//! assert!(!tracker.is_healthy(0.7));
+//!
+//! for _ in 0..2 {
+//!     tracker.record_success();
+//! }
+//! assert!(tracker.is_healthy(0.7));
+//! ```
+
+use crate::{CountingRatio, WindowedRatio};
+
+#[derive(Clone, Debug)]
+pub struct SuccessTracker {
+    recent: WindowedRatio,
+    hysteresis: f64,
+    min_observations: u64,
+    healthy: bool,
+}
+
+impl SuccessTracker {
+    /// Tracks the success rate of the most recent `window` calls, starting out healthy.
+    pub fn new(window: usize) -> Self {
+        Self {
+            recent: WindowedRatio::new(window),
+            hysteresis: 0.0,
+            min_observations: 0,
+            healthy: true,
+        }
+    }
+
+    /// Once unhealthy, the success rate must reach the threshold plus `margin` to recover.
+    pub fn with_hysteresis(self, margin: f64) -> Self {
+        assert!(margin >= 0.0, "hysteresis margin must not be negative");
+        Self {
+            hysteresis: margin,
+            ..self
+        }
+    }
+
+    /// The health does not change until at least `min_observations` calls are in the
+    /// window, so that a few early failures cannot trip the tracker.
+    pub fn with_min_observations(self, min_observations: u64) -> Self {
+        Self {
+            min_observations,
+            ..self
+        }
+    }
+
+    pub fn record_success(&mut self) {
+        self.recent.observe(true);
+    }
+
+    pub fn record_failure(&mut self) {
+        self.recent.observe(false);
+    }
+
+    pub fn record(&mut self, success: bool) {
+        self.recent.observe(success);
+    }
+
+    /// The successes among the calls in the window.
+    pub fn success_ratio(&self) -> CountingRatio {
+        self.recent.ratio()
+    }
+
+    /// Updates and returns the health given a minimum acceptable success rate of
+    /// `threshold`.
+    pub fn is_healthy(&mut self, threshold: f64) -> bool {
+        let ratio = self.recent.ratio();
+        if ratio.observations() >= self.min_observations {
+            if let Some(rate) = ratio.value() {
+                if self.healthy {
+                    self.healthy = rate >= threshold;
+                } else {
+                    self.healthy = rate >= threshold + self.hysteresis;
+                }
+            }
+        }
+        self.healthy
+    }
+}
@@ -0,0 +1,59 @@
+//! A `ProportionSummary` gathers the usual descriptive statistics of an observed
+//! proportion: the point estimate, the variance of that estimate, its standard error, and the
+//! margin of error at a chosen normal quantile.
+//!
+//! ```
+//! use counting_ratio::CountingRatio;
+//!
+//! let summary = CountingRatio::ratio(15, 100).summary(1.96).unwrap();
+//! assert_eq!(summary.estimate, 0.15);
+//! assert!((summary.variance - 0.001275).abs() < 1e-12);
+//! assert!((summary.standard_error - 0.035707).abs() < 1e-6);
+//! assert!((summary.margin_of_error - 0.069986).abs() < 1e-6);
+//!
+//! let (low, high) = summary.interval();
+//! assert!((low - 0.080014).abs() < 1e-6 && (high - 0.219986).abs() < 1e-6);
+//! assert_eq!(CountingRatio::new().summary(1.96), None);
+//! ```
+
+use crate::{Count, GenericCountingRatio};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProportionSummary {
+    /// The observed proportion `p`.
+    pub estimate: f64,
+    /// The variance `p(1 - p) / n` of the estimate over `n` Bernoulli observations.
+    pub variance: f64,
+    /// The square root of the variance.
+    pub standard_error: f64,
+    /// The standard error times the normal quantile `z`.
+    pub margin_of_error: f64,
+}
+
+impl ProportionSummary {
+    /// The estimate plus or minus the margin of error, which is the Wald interval. Unlike
+    /// `wilson_interval()`, it is not clamped to `[0.0, 1.0]`.
+    pub fn interval(&self) -> (f64, f64) {
+        (
+            self.estimate - self.margin_of_error,
+            self.estimate + self.margin_of_error,
+        )
+    }
+}
+
+impl<T: Count> GenericCountingRatio<T> {
+    /// Summarizes the observed proportion, where `z` is the standard normal quantile for the
+    /// desired confidence (e.g. 1.96 for 95%). Returns `None` if the ratio is undefined.
+    pub fn summary(&self, z: f64) -> Option<ProportionSummary> {
+        let estimate = self.value()?;
+        let (_, n) = self.counts_f64();
+        let variance = estimate * (1.0 - estimate) / n;
+        let standard_error = variance.sqrt();
+        Some(ProportionSummary {
+            estimate,
+            variance,
+            standard_error,
+            margin_of_error: z * standard_error,
+        })
+    }
+}
@@ -0,0 +1,168 @@
+//! A `TwoByTwoTable` cross-classifies observations by a binary exposure and a binary
+//! outcome. It extends the prior/posterior idea of `observe_with_prior()` by keeping the
+//! outcome ratio for both the exposed and the unexposed observations.
+//!
+//! ```
+//! use counting_ratio::{CountingRatio, TwoByTwoTable};
+//!
+//! let mut table = TwoByTwoTable::new();
+//! for _ in 0..20 { table.observe(true, true); }
+//! for _ in 0..80 { table.observe(true, false); }
+//! for _ in 0..10 { table.observe(false, true); }
+//! for _ in 0..90 { table.observe(false, false); }
+//!
+//! assert_eq!(table.exposed(), CountingRatio::ratio(20, 100));
+//! assert_eq!(table.unexposed(), CountingRatio::ratio(10, 100));
+//! assert_eq!(table.relative_risk(), Some(2.0));
+//! assert_eq!(table.odds_ratio(), Some(2.25));
+//! assert!((table.risk_difference().unwrap() - 0.1).abs() < 1e-12);
+//!
+//! let (low, high) = table.odds_ratio_interval(1.96).unwrap();
+//! assert!(low < 2.25 && 2.25 < high);
+//! ```
+
+use crate::CountingRatio;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoByTwoTable {
+    exposed: CountingRatio,
+    unexposed: CountingRatio,
+}
+
+impl TwoByTwoTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a table from the outcome ratios of the exposed and unexposed observations.
+    pub fn from_ratios(exposed: CountingRatio, unexposed: CountingRatio) -> Self {
+        Self { exposed, unexposed }
+    }
+
+    pub fn observe(&mut self, exposure: bool, outcome: bool) {
+        if exposure {
+            self.exposed.observe(outcome);
+        } else {
+            self.unexposed.observe(outcome);
+        }
+    }
+
+    /// The proportion of exposed observations with the outcome.
+    pub fn exposed(&self) -> CountingRatio {
+        self.exposed
+    }
+
+    /// The proportion of unexposed observations with the outcome.
+    pub fn unexposed(&self) -> CountingRatio {
+        self.unexposed
+    }
+
+    /// The proportion of observations with the outcome, regardless of exposure.
+    pub fn outcome(&self) -> CountingRatio {
+        self.exposed + self.unexposed
+    }
+
+    /// The proportion of observations that were exposed.
+    pub fn exposure(&self) -> CountingRatio {
+        CountingRatio::ratio(
+            self.exposed.observations(),
+            self.exposed.observations() + self.unexposed.observations(),
+        )
+    }
+
+    // The cells of the table, ordered as exposed with and without the outcome, then
+    // unexposed with and without the outcome.
+    fn cells(&self) -> (f64, f64, f64, f64) {
+        (
+            self.exposed.matches() as f64,
+            self.exposed.misses() as f64,
+            self.unexposed.matches() as f64,
+            self.unexposed.misses() as f64,
+        )
+    }
+
+    pub fn risk_difference(&self) -> Option<f64> {
+        Some(self.exposed.value()? - self.unexposed.value()?)
+    }
+
+    pub fn relative_risk(&self) -> Option<f64> {
+        let unexposed = self.unexposed.value()?;
+        if unexposed == 0.0 {
+            None
+        } else {
+            Some(self.exposed.value()? / unexposed)
+        }
+    }
+
+    pub fn odds_ratio(&self) -> Option<f64> {
+        let (a, b, c, d) = self.cells();
+        if b * c == 0.0 {
+            None
+        } else {
+            Some(a * d / (b * c))
+        }
+    }
+
+    /// The phi coefficient, which measures the association between exposure and outcome
+    /// on a scale from -1 to 1.
+    pub fn phi(&self) -> Option<f64> {
+        let (a, b, c, d) = self.cells();
+        let denominator = ((a + b) * (c + d) * (a + c) * (b + d)).sqrt();
+        if denominator == 0.0 {
+            None
+        } else {
+            Some((a * d - b * c) / denominator)
+        }
+    }
+
+    /// Wald interval for the risk difference, where `z` is the standard normal quantile
+    /// for the desired confidence.
+    pub fn risk_difference_interval(&self, z: f64) -> Option<(f64, f64)> {
+        let difference = self.risk_difference()?;
+        let variance = [self.exposed, self.unexposed]
+            .iter()
+            .map(|ratio| {
+                let p = f64::from(*ratio);
+                p * (1.0 - p) / ratio.observations() as f64
+            })
+            .sum::<f64>();
+        let margin = z * variance.sqrt();
+        Some((difference - margin, difference + margin))
+    }
+
+    /// Interval for the relative risk computed on the log scale. Requires every exposure
+    /// group to include at least one observation with the outcome.
+    pub fn relative_risk_interval(&self, z: f64) -> Option<(f64, f64)> {
+        let (a, _, c, _) = self.cells();
+        if a == 0.0 || c == 0.0 {
+            return None;
+        }
+        let n1 = self.exposed.observations() as f64;
+        let n2 = self.unexposed.observations() as f64;
+        let standard_error = (1.0 / a - 1.0 / n1 + 1.0 / c - 1.0 / n2).sqrt();
+        Some(log_scale_interval(self.relative_risk()?, standard_error, z))
+    }
+
+    /// Woolf's interval for the odds ratio, computed on the log scale. Requires every cell
+    /// of the table to be nonzero.
+    pub fn odds_ratio_interval(&self, z: f64) -> Option<(f64, f64)> {
+        let (a, b, c, d) = self.cells();
+        if a * b * c * d == 0.0 {
+            return None;
+        }
+        let standard_error = (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt();
+        Some(log_scale_interval(self.odds_ratio()?, standard_error, z))
+    }
+
+    /// Fisher's exact test of association between exposure and outcome.
+    pub fn fisher_exact(&self) -> f64 {
+        crate::fisher_exact(self.exposed, self.unexposed)
+    }
+}
+
+fn log_scale_interval(estimate: f64, standard_error: f64, z: f64) -> (f64, f64) {
+    let center = estimate.ln();
+    let margin = z * standard_error;
+    ((center - margin).exp(), (center + margin).exp())
+}
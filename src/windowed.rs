@@ -0,0 +1,82 @@
+//! A `WindowedRatio` only remembers the most recent observations, discarding older ones
+//! as new ones arrive. This is useful for tracking the recent success rate of a
+//! long-running process.
+//!
+//! ```
+//! use counting_ratio::WindowedRatio;
+//!
+//! let mut recent = WindowedRatio::new(4);
+//! for outcome in [false, false, true, true, true, false] {
+//!     recent.observe(outcome);
+//! }
+//!
+//! assert_eq!("3/4 (75.00%)", format!("{recent}").as_str());
+//! ```
+
+use crate::CountingRatio;
+use alloc::collections::VecDeque;
+use core::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug)]
+pub struct WindowedRatio {
+    window: VecDeque<bool>,
+    capacity: usize,
+    current: CountingRatio,
+}
+
+impl WindowedRatio {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "WindowedRatio capacity must be positive");
+        Self {
+            window: VecDeque::with_capacity(capacity),
+            capacity,
+            current: CountingRatio::new(),
+        }
+    }
+
+    pub fn observe(&mut self, condition_met: bool) {
+        if self.window.len() == self.capacity {
+            if let Some(oldest) = self.window.pop_front() {
+                self.current.unobserve(oldest);
+            }
+        }
+        self.window.push_back(condition_met);
+        self.current.observe(condition_met);
+    }
+
+    pub fn observe_with_prior(&mut self, prior_condition_met: bool, posterior_condition_met: bool) {
+        if prior_condition_met {
+            self.observe(posterior_condition_met);
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn defined(&self) -> bool {
+        self.current.defined()
+    }
+
+    pub fn ratio(&self) -> CountingRatio {
+        self.current
+    }
+}
+
+impl From<&WindowedRatio> for CountingRatio {
+    fn from(windowed: &WindowedRatio) -> Self {
+        windowed.ratio()
+    }
+}
+
+impl From<&WindowedRatio> for f64 {
+    fn from(windowed: &WindowedRatio) -> Self {
+        windowed.ratio().into()
+    }
+}
+
+impl Display for WindowedRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.current, f)
+    }
+}